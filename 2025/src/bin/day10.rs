@@ -0,0 +1,8 @@
+use advent_of_code_common::memory::CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn main() {
+    advent_of_code_2025::runner::day_main(10);
+}
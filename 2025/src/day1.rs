@@ -1,147 +1,1066 @@
 use std::{
-    env,
-    fs::File,
-    io::{BufRead, BufReader},
-    process,
+    collections::BTreeMap,
+    error::Error,
+    fmt::{self, Display},
+    io::BufRead,
 };
 
-fn main() {
-    println!("----- Day 1 -----");
+use advent_of_code_common::{
+    Solution,
+    bigint::BigUint,
+    error::{InputError, ParseError, parse_number},
+    export::Export,
+    tool::{Tool, ToolError},
+    trace,
+};
+
+pub struct Day1;
+
+/// Number of positions on the puzzle's dial.
+const DIAL_SIZE: u64 = 100;
+
+/// Position the puzzle's dial starts at.
+const DIAL_START: u64 = 50;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Rotation {
+    direction: Direction,
+    /// Distances can have any number of digits.
+    distance: BigUint,
+}
+
+/// Circular dial numbered from 0 up to `modulus - 1`, where turning right counts up.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Dial {
+    modulus: u64,
+    position: u64,
+}
+
+/// What happened during a single rotation of a [`Dial`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RotationEvent {
+    pub start: u64,
+    pub end: u64,
+    /// Whether the dial stopped on 0.
+    pub landed_on_zero: bool,
+    /// Number of clicks where the dial pointed at 0, including where it stopped.
+    pub zero_passes: BigUint,
+}
+
+/// Running totals of both parts' answers over a sequence of rotations.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct ZeroCounts {
+    /// Number of rotations that stopped on 0 (part 1).
+    pub landed: u64,
+    /// Number of clicks that pointed at 0 (part 2).
+    pub passes: BigUint,
+}
+
+/// Several named dials that rotate independently, like the wheels of a combination lock.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Lock {
+    dials: BTreeMap<String, Dial>,
+}
+
+/// Reasons a [`Dial`] or [`Lock`] can't be set up, run some steps or look for a combination.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LockError {
+    /// A dial needs at least one position to point at.
+    EmptyDial,
+    StartOffDial {
+        modulus: u64,
+        start: u64,
+    },
+    UnknownDial(String),
+    /// A combination without any dials would match before the first step.
+    EmptyCombination,
+    /// A combination can only have one position for each dial.
+    DuplicateDial(String),
+    PositionOffDial {
+        dial: String,
+        position: u64,
+    },
+}
+
+/// Rotation of one of a [`Lock`]'s dials, written like `A:L39`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LockStep {
+    pub dial: String,
+    pub rotation: Rotation,
+}
+
+/// Number of clicks that pointed at each position of a [`Dial`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PositionHistogram {
+    /// Whole revolutions, which point at every position once.
+    revolutions: BigUint,
+    /// Clicks from the rest of each rotation, as a difference array so each rotation takes
+    /// constant time (the count for a position is the sum of everything up to its index).
+    partial_clicks: Vec<i64>,
+}
+
+impl Display for Rotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = match self.direction {
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+        };
+
+        // Padded as a whole, so rotations line up in trace output
+        f.pad(&format!("{}{}", direction, self.distance))
+    }
+}
+
+impl Dial {
+    /// Creates a dial with `modulus` positions, pointing at `start`.
+    ///
+    /// Fails if the dial has no positions, or `start` isn't one of them.
+    pub fn new(modulus: u64, start: u64) -> Result<Self, LockError> {
+        if modulus == 0 {
+            return Err(LockError::EmptyDial);
+        }
+
+        if start >= modulus {
+            return Err(LockError::StartOffDial { modulus, start });
+        }
+
+        Ok(Dial {
+            modulus,
+            position: start,
+        })
+    }
 
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("No input file specified");
-        process::exit(1);
+    pub const fn position(&self) -> u64 {
+        self.position
     }
 
-    let input_path = &args[1];
-    println!("Input File: {}", input_path);
+    /// Turns the dial one click at a time for `distance` clicks.
+    ///
+    /// This takes constant time no matter how large the distance is: every whole revolution
+    /// points at 0 exactly once, so only the remaining part of a revolution needs working out.
+    pub fn rotate(&mut self, direction: Direction, distance: &BigUint) -> RotationEvent {
+        let mut revolutions = distance.clone();
+        let remainder = revolutions.div_rem_small(self.modulus) as u128;
+
+        let start = self.position;
+        let (m, position) = (self.modulus as u128, start as u128);
+
+        /*
+           While the problem was easy to understand, I had trouble with two edge cases LOL
+
+           L39 -> Position: 0, Zeroes: 6466
+           L17 -> Position: 83, Zeroes: 6467 <- shouldn't increment here as it never transitioned to 0
+           ...
+           L8 -> Position: 95, Zeroes: 6452
+           L95 -> Position: 0, Zeroes: 6452 <- should've incremented here as it landed on 0
+
+           Both go away by counting clicks rather than wraps: the dial is `clicks_from_zero`
+           clicks past the last time it pointed at 0 (going in this direction), so the remaining
+           clicks reach 0 again if they add up to a full revolution.
+        */
+        let (end, clicks_from_zero) = match direction {
+            Direction::Left => ((position + m - remainder) % m, (m - position) % m),
+            Direction::Right => ((position + remainder) % m, position),
+        };
+
+        self.position = end as u64;
 
-    println!("Part 1: {}", solve_part_1(input_path));
-    println!("Part 2: {}", solve_part_2(input_path));
+        RotationEvent {
+            start,
+            end: end as u64,
+            landed_on_zero: end == 0,
+            zero_passes: revolutions + BigUint::from(((clicks_from_zero + remainder) / m) as u64),
+        }
+    }
 }
 
-/// Counts how many times the dial lands on 0.
-fn solve_part_1(input_filepath: &str) -> i32 {
-    let input_file = File::open(input_filepath).expect("Unable to open input file");
-    let input_reader = BufReader::new(input_file);
+impl Default for Dial {
+    /// The puzzle's dial.
+    fn default() -> Self {
+        Dial {
+            modulus: DIAL_SIZE,
+            position: DIAL_START,
+        }
+    }
+}
 
-    let mut position = 50;
-    let mut zeroes = 0;
+impl PositionHistogram {
+    /// Creates an empty histogram for a dial with `modulus` positions.
+    pub fn new(modulus: u64) -> Self {
+        PositionHistogram {
+            revolutions: BigUint::zero(),
+            partial_clicks: vec![0; modulus as usize + 1],
+        }
+    }
 
-    for input_line in input_reader
-        .lines()
-        .map_while(|line| line.ok().filter(|x| !x.is_empty()))
-    {
-        let (direction, distance_str) = input_line.split_at(1);
-        let mut distance = distance_str.parse::<i32>().expect(&format!(
-            "Unable to parse distance value '{}'",
-            distance_str
-        ));
+    /// Records every click of turning the dial from `start`, not counting `start` itself.
+    pub fn record(&mut self, start: u64, direction: Direction, distance: &BigUint) {
+        let modulus = self.partial_clicks.len() - 1;
+        let mut revolutions = distance.clone();
+        let remainder = revolutions.div_rem_small(modulus as u64) as usize;
+        self.revolutions += revolutions;
 
-        // Ignore extra revolutions
-        distance %= 100;
+        // The rest of the rotation points at `remainder` positions in a row, possibly wrapping
+        let start = start as usize;
+        let first = match direction {
+            Direction::Left => (start + modulus - remainder) % modulus,
+            Direction::Right => (start + 1) % modulus,
+        };
 
-        if distance <= 0 {
-            // Nothing to do
-            continue;
+        let end = first + remainder;
+        self.partial_clicks[first] += 1;
+        if end <= modulus {
+            self.partial_clicks[end] -= 1;
+        } else {
+            self.partial_clicks[modulus] -= 1;
+            self.partial_clicks[0] += 1;
+            self.partial_clicks[end - modulus] -= 1;
         }
+    }
 
-        // Handle remaining distance within range - guaranteed to be in range [1, 99]
-        match direction {
-            "L" => {
-                position -= distance;
-                if position < 0 {
-                    position += 100;
-                }
+    /// Number of clicks that pointed at each position, in position order.
+    pub fn visits(&self) -> Vec<BigUint> {
+        let modulus = self.partial_clicks.len() - 1;
+
+        self.partial_clicks[..modulus]
+            .iter()
+            .scan(0, |clicks, &change| {
+                *clicks += change;
+                Some(&self.revolutions + &BigUint::from(*clicks as u64))
+            })
+            .collect()
+    }
+}
+
+impl Lock {
+    /// Creates a lock from named dials, which can each have their own size and start.
+    pub fn new<'a>(dials: impl IntoIterator<Item = (&'a str, Dial)>) -> Self {
+        Lock {
+            dials: dials
+                .into_iter()
+                .map(|(name, dial)| (name.to_string(), dial))
+                .collect(),
+        }
+    }
+
+    /// Creates a lock with the puzzle's dial for every name used in the steps.
+    pub fn for_steps(steps: &[LockStep]) -> Self {
+        Lock::new(
+            steps
+                .iter()
+                .map(|step| (step.dial.as_str(), Dial::default())),
+        )
+    }
+
+    /// Position of the named dial, or `None` if the lock has no such dial.
+    pub fn position(&self, name: &str) -> Option<u64> {
+        self.dials.get(name).map(Dial::position)
+    }
+
+    pub fn all_at_zero(&self) -> bool {
+        self.dials.values().all(|dial| dial.position() == 0)
+    }
+
+    /// Whether every dial in the combination is at its position. Other dials can be anywhere.
+    pub fn matches(&self, combination: &[(&str, u64)]) -> bool {
+        combination
+            .iter()
+            .all(|&(name, position)| self.position(name) == Some(position))
+    }
+
+    /// Rotates one of the dials, leaving the rest where they are.
+    pub fn rotate(&mut self, step: &LockStep) -> Result<RotationEvent, LockError> {
+        let dial = self
+            .dials
+            .get_mut(&step.dial)
+            .ok_or_else(|| LockError::UnknownDial(step.dial.clone()))?;
+
+        let event = dial.rotate(step.rotation.direction, &step.rotation.distance);
+        trace!("{}:{} -> {:?}", step.dial, step.rotation, event);
+
+        Ok(event)
+    }
+
+    /// Applies the steps in order, counting the steps after which every dial is at 0.
+    pub fn count_all_at_zero(&mut self, steps: &[LockStep]) -> Result<usize, LockError> {
+        let mut count = 0;
+        for step in steps {
+            self.rotate(step)?;
+            count += self.all_at_zero() as usize;
+        }
+
+        Ok(count)
+    }
+
+    /// Applies the steps in order until the dials are at the target combination, returning the
+    /// step it was reached after (counting from 1, or 0 if the dials start there), if it ever is.
+    ///
+    /// The combination has to name at least one dial, and only dials that are in the lock, each
+    /// of them once.
+    pub fn first_combination(
+        &mut self,
+        steps: &[LockStep],
+        combination: &[(&str, u64)],
+    ) -> Result<Option<usize>, LockError> {
+        if combination.is_empty() {
+            return Err(LockError::EmptyCombination);
+        }
+
+        for (idx, &(name, position)) in combination.iter().enumerate() {
+            if combination[..idx]
+                .iter()
+                .any(|&(earlier, _)| earlier == name)
+            {
+                return Err(LockError::DuplicateDial(name.to_string()));
             }
-            "R" => {
-                position += distance;
-                if position > 99 {
-                    position -= 100;
-                }
+
+            let dial = self
+                .dials
+                .get(name)
+                .ok_or_else(|| LockError::UnknownDial(name.to_string()))?;
+
+            if position >= dial.modulus {
+                return Err(LockError::PositionOffDial {
+                    dial: name.to_string(),
+                    position,
+                });
             }
-            _ => panic!("Unexpected direction {}", direction),
         }
 
-        if position == 0 {
-            zeroes += 1;
+        if self.matches(combination) {
+            return Ok(Some(0));
         }
+
+        for (idx, step) in steps.iter().enumerate() {
+            self.rotate(step)?;
+            if self.matches(combination) {
+                return Ok(Some(idx + 1));
+            }
+        }
+
+        Ok(None)
     }
+}
 
-    zeroes
+impl Display for LockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LockError::EmptyDial => write!(f, "Dial must have at least one position"),
+            LockError::StartOffDial { modulus, start } => {
+                write!(
+                    f,
+                    "Dial with {} positions can't start at {}",
+                    modulus, start
+                )
+            }
+            LockError::UnknownDial(name) => write!(f, "Lock has no dial '{}'", name),
+            LockError::EmptyCombination => write!(f, "Combination must name at least one dial"),
+            LockError::DuplicateDial(name) => {
+                write!(f, "Combination names dial '{}' more than once", name)
+            }
+            LockError::PositionOffDial { dial, position } => {
+                write!(f, "Dial '{}' has no position {}", dial, position)
+            }
+        }
+    }
 }
 
-/// Counts the number of times the dial passes or lands on 0.
-fn solve_part_2(input_filepath: &str) -> i32 {
-    let input_file = File::open(input_filepath).expect("Unable to open input file");
-    let input_reader = BufReader::new(input_file);
+impl Error for LockError {}
 
-    /*
-       While the problem was easy to understand, I had trouble with two edge cases LOL
+impl ZeroCounts {
+    fn add(&mut self, event: RotationEvent) {
+        self.landed += event.landed_on_zero as u64;
+        self.passes += event.zero_passes;
+    }
+}
+
+/// Turns the puzzle's dial through every rotation, in order.
+fn simulate(rotations: &[Rotation]) -> impl Iterator<Item = RotationEvent> + '_ {
+    let mut dial = Dial::default();
+
+    rotations.iter().map(move |rotation| {
+        let event = dial.rotate(rotation.direction, &rotation.distance);
+        trace!("{:>2} + {:<4} -> {:?}", event.start, rotation, event);
+
+        event
+    })
+}
+
+/// Counts the answers for both parts while reading one rotation at a time, so inputs with any
+/// number of lines can be handled without holding them in memory.
+pub fn count_zeroes(reader: impl BufRead, mut dial: Dial) -> Result<ZeroCounts, InputError> {
+    let mut counts = ZeroCounts::default();
+
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            break;
+        }
+
+        let rotation = parse_rotation(idx + 1, line, line)?;
+        let event = dial.rotate(rotation.direction, &rotation.distance);
+        trace!("{:>2} + {:<4} -> {:?}", event.start, rotation, event);
+
+        counts.add(event);
+    }
+
+    Ok(counts)
+}
+
+/// Streaming solver for the runner, which answers both parts using [`count_zeroes`].
+pub fn stream(reader: &mut dyn BufRead) -> Result<Vec<String>, InputError> {
+    let counts = count_zeroes(reader, Dial::default())?;
 
-       L39 -> Position: 0, Zeroes: 6466
-       L17 -> Position: 83, Zeroes: 6467 <- shouldn't increment here as it never transitioned to 0
-       ...
-       L8 -> Position: 95, Zeroes: 6452
-       L95 -> Position: 0, Zeroes: 6452 <- should've incremented here as it landed on 0
-    */
-    let mut position = 50;
-    let mut zeroes = 0;
+    Ok(vec![counts.landed.to_string(), counts.passes.to_string()])
+}
+
+/// Exports a timeline of where each rotation started and ended and how many times it pointed at
+/// 0, the rotations that pointed at 0 on their own, and how many times each position was
+/// pointed at.
+pub fn export(input: &str) -> Result<Vec<Export>, ParseError> {
+    let rotations = Day1::parse(input)?;
+    let mut histogram = PositionHistogram::new(DIAL_SIZE);
+    let mut timeline = Vec::with_capacity(rotations.len());
+    let mut zero_events = Vec::new();
+
+    for (idx, (rotation, event)) in rotations.iter().zip(simulate(&rotations)).enumerate() {
+        histogram.record(event.start, rotation.direction, &rotation.distance);
+
+        timeline.push(format!(
+            "{},{},{},{},{},{}",
+            idx + 1,
+            rotation,
+            event.start,
+            event.end,
+            event.zero_passes,
+            event.landed_on_zero
+        ));
+
+        if !event.zero_passes.is_zero() {
+            zero_events.push(format!(
+                "{},{},{},{}",
+                idx + 1,
+                rotation,
+                event.zero_passes,
+                event.landed_on_zero
+            ));
+        }
+    }
+
+    let positions = histogram
+        .visits()
+        .iter()
+        .enumerate()
+        .map(|(position, visits)| format!("{},{}", position, visits))
+        .collect();
+
+    Ok(vec![
+        Export {
+            name: "timeline",
+            header: "instruction,rotation,start,end,zero_passes,landed_on_zero",
+            rows: timeline,
+        },
+        Export {
+            name: "zero_events",
+            header: "instruction,rotation,zero_passes,landed_on_zero",
+            rows: zero_events,
+        },
+        Export {
+            name: "positions",
+            header: "position,visits",
+            rows: positions,
+        },
+    ])
+}
 
-    for input_line in input_reader
+/// Parses one rotation per line for a [`Lock`], each prefixed by the dial's name, e.g. `A:L39`.
+pub fn parse_lock_steps(input: &str) -> Result<Vec<LockStep>, ParseError> {
+    input
         .lines()
-        .map_while(|line| line.ok().filter(|x| !x.is_empty()))
-    {
-        let (direction, distance_str) = input_line.split_at(1);
-        let mut distance = distance_str.parse::<i32>().expect(&format!(
-            "Unable to parse distance value '{}'",
-            distance_str
+        .map_while(|line| Some(line.trim()).filter(|x| !x.is_empty()))
+        .enumerate()
+        .map(|(idx, line)| {
+            let Some((dial, rotation_str)) = line.split_once(':') else {
+                return Err(ParseError::new(
+                    idx + 1,
+                    line,
+                    line,
+                    "Expected a dial name and a rotation, like 'A:L39'",
+                ));
+            };
+
+            if dial.is_empty() || dial.contains(char::is_whitespace) {
+                return Err(ParseError::new(idx + 1, line, dial, "Invalid dial name"));
+            }
+
+            Ok(LockStep {
+                dial: dial.to_string(),
+                rotation: parse_rotation(idx + 1, line, rotation_str)?,
+            })
+        })
+        .collect()
+}
+
+/// Parses a combination of dial positions like `A=0,B=25`.
+pub fn parse_combination(combination_str: &str) -> Result<Vec<(&str, u64)>, ParseError> {
+    combination_str
+        .split(',')
+        .map(|item| {
+            let Some((dial, position_str)) = item.split_once('=') else {
+                return Err(ParseError::new(
+                    1,
+                    combination_str,
+                    item,
+                    "Expected a dial name and a position, like 'A=0'",
+                ));
+            };
+
+            if dial.is_empty() || dial.contains(char::is_whitespace) {
+                return Err(ParseError::new(
+                    1,
+                    combination_str,
+                    dial,
+                    "Invalid dial name",
+                ));
+            }
+
+            let position = parse_number(1, combination_str, position_str)?;
+            Ok((dial, position))
+        })
+        .collect()
+}
+
+/// Parses a dial's name, number of positions and start position, like `A=100@50`.
+pub fn parse_dial(dial_str: &str) -> Result<(&str, Dial), ParseError> {
+    let Some((name, size_str)) = dial_str.split_once('=') else {
+        return Err(ParseError::new(
+            1,
+            dial_str,
+            dial_str,
+            "Expected a dial name, size and start, like 'A=100@50'",
         ));
+    };
 
-        // Count extra revolutions that are guaranteed to go past 0
-        zeroes += distance / 100;
-        distance %= 100;
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return Err(ParseError::new(1, dial_str, name, "Invalid dial name"));
+    }
 
-        if distance <= 0 {
-            continue;
-        }
+    let Some((modulus_str, start_str)) = size_str.split_once('@') else {
+        return Err(ParseError::new(
+            1,
+            dial_str,
+            size_str,
+            "Expected a size and start, like '100@50'",
+        ));
+    };
+
+    let modulus = parse_number(1, dial_str, modulus_str)?;
+    let start = parse_number(1, dial_str, start_str)?;
+    let dial = Dial::new(modulus, start)
+        .map_err(|e| ParseError::new(1, dial_str, size_str, e.to_string()))?;
+
+    Ok((name, dial))
+}
+
+/// `aoc lock`, which turns a [`Lock`] through the steps in its input.
+pub const LOCK_TOOL: Tool = Tool {
+    name: "lock",
+    options: "[--dial NAME=SIZE@START]... [--target COMBINATION]",
+    description: "\
+Turns a lock of day 1 dials, with one rotation per line prefixed by the dial's name
+(e.g. 'A:L39'). Dials are like the puzzle's unless configured with a size and start
+(e.g. 'A=10@0'). Prints how many steps left every dial at 0, and the first step that
+reached the target combination (e.g. 'A=0,B=25')",
+    run: run_lock,
+};
 
-        // Handle remaining distance within range - guaranteed to be in range [1, 99]
-        let original_position = position;
-        match direction {
-            "L" => {
-                position -= distance;
+fn run_lock(input: &str, args: &[String]) -> Result<Vec<String>, ToolError> {
+    let mut dials: Vec<(&str, Dial)> = Vec::new();
+    let mut target: Option<(&str, Vec<(&str, u64)>)> = None;
 
-                if position == 0 {
-                    // Dial landed on 0
-                    zeroes += 1;
-                } else if position < 0 {
-                    position += 100;
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        let mut flag_value = || {
+            args_iter
+                .next()
+                .ok_or_else(|| ToolError::Usage(format!("Missing value for {}", arg)))
+        };
 
-                    // If we didn't start from 0, means that dial went past it
-                    if original_position != 0 {
-                        zeroes += 1;
-                    }
+        match arg.as_str() {
+            "--dial" => {
+                let dial_arg = flag_value()?;
+                let (name, dial) = parse_dial(dial_arg).map_err(|e| {
+                    ToolError::Usage(format!("{}\nInvalid dial '{}'", e.render(), dial_arg))
+                })?;
+
+                if dials.iter().any(|&(configured, _)| configured == name) {
+                    return Err(ToolError::Usage(format!(
+                        "Dial '{}' is configured more than once",
+                        name
+                    )));
                 }
+
+                dials.push((name, dial));
             }
-            "R" => {
-                position += distance;
+            "--target" => {
+                let target_arg = flag_value()?;
+                let combination = parse_combination(target_arg).map_err(|e| {
+                    ToolError::Usage(format!("{}\nInvalid target '{}'", e.render(), target_arg))
+                })?;
 
-                // Check if dial wrapped back around
-                if position > 99 {
-                    position -= 100;
-                    zeroes += 1;
-                }
+                target = Some((target_arg, combination));
+            }
+            value => {
+                return Err(ToolError::Usage(format!("Unexpected argument '{}'", value)));
             }
-            _ => panic!("Unexpected direction {}", direction),
         }
+    }
+
+    let steps = parse_lock_steps(input)?;
+
+    // Any dial that wasn't configured is the puzzle's dial
+    let unconfigured = steps
+        .iter()
+        .filter(|step| !dials.iter().any(|&(name, _)| name == step.dial))
+        .map(|step| (step.dial.as_str(), Dial::default()));
+    let lock = Lock::new(dials.iter().copied().chain(unconfigured));
+
+    // Run both queries before printing, so an invalid target fails without any partial output
+    let count = lock
+        .clone()
+        .count_all_at_zero(&steps)
+        .map_err(|e| ToolError::Failed(e.to_string()))?;
+
+    let mut output = vec![format!("All dials at 0: {} step(s)", count)];
+
+    if let Some((target_arg, combination)) = target {
+        let first_step = lock
+            .clone()
+            .first_combination(&steps, &combination)
+            .map_err(|e| ToolError::Failed(e.to_string()))?;
+
+        output.push(match first_step {
+            Some(step) => format!("{}: first reached at step {}", target_arg, step),
+            None => format!("{}: never reached", target_arg),
+        });
+    }
+
+    Ok(output)
+}
+
+/// Parses a rotation like `L68`, which is either the whole input line or the end of one.
+fn parse_rotation(
+    line_number: usize,
+    input_line: &str,
+    rotation_str: &str,
+) -> Result<Rotation, ParseError> {
+    let direction_len = rotation_str.chars().next().map_or(0, |c| c.len_utf8());
+    let (direction_str, distance_str) = rotation_str.split_at(direction_len);
+
+    let direction = match direction_str {
+        "L" => Direction::Left,
+        "R" => Direction::Right,
+        _ => {
+            return Err(ParseError::new(
+                line_number,
+                input_line,
+                direction_str,
+                "Unexpected direction",
+            ));
+        }
+    };
+
+    if distance_str.starts_with('-') {
+        return Err(ParseError::new(
+            line_number,
+            input_line,
+            distance_str,
+            "Distance must not be negative",
+        ));
+    }
+
+    let distance = parse_number::<BigUint>(line_number, input_line, distance_str)?;
+
+    Ok(Rotation {
+        direction,
+        distance,
+    })
+}
+
+impl Solution for Day1 {
+    type Input = Vec<Rotation>;
+    type Answer1 = usize;
+    type Answer2 = BigUint;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .map_while(|line| Some(line.trim()).filter(|x| !x.is_empty()))
+            .enumerate()
+            .map(|(idx, line)| parse_rotation(idx + 1, line, line))
+            .collect()
+    }
+
+    /// Counts how many times the dial lands on 0.
+    fn part1(rotations: &Self::Input) -> Self::Answer1 {
+        simulate(rotations).filter(|e| e.landed_on_zero).count()
+    }
+
+    /// Counts the number of times the dial passes or lands on 0.
+    fn part2(rotations: &Self::Input) -> Self::Answer2 {
+        simulate(rotations).map(|e| e.zero_passes).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day1::solve_part_1(EXAMPLE).unwrap(), 3);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day1::solve_part_2(EXAMPLE).unwrap(), 6);
+    }
+
+    #[test]
+    fn part1_whole_revolution_from_zero_counts() {
+        // Found by differential testing: the dial starts and ends the R100 on 0
+        assert_eq!(Day1::solve_part_1("R50\nR100\n").unwrap(), 2);
+    }
+
+    #[test]
+    fn part2_leaving_zero_does_not_count() {
+        // Lands on 0, then moves left without passing 0 again
+        assert_eq!(Day1::solve_part_2("R50\nL17\n").unwrap(), 1);
+    }
+
+    #[test]
+    fn part2_landing_on_zero_from_right_counts() {
+        assert_eq!(Day1::solve_part_2("L45\nL5\n").unwrap(), 1);
+    }
 
-        // println!(
-        //     "{:>2} + {:<4} -> Position: {:>2}, Zeroes: {}",
-        //     original_position, &input_line, position, zeroes
-        // );
+    #[test]
+    fn part2_counts_full_revolutions() {
+        assert_eq!(Day1::solve_part_2("R1000\n").unwrap(), 10);
+        assert_eq!(Day1::solve_part_2("L250\n").unwrap(), 3);
     }
 
-    zeroes
+    #[test]
+    fn streams_example() {
+        let answers = stream(&mut EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(answers, ["3", "6"]);
+    }
+
+    #[test]
+    fn pads_rotations() {
+        let rotation = parse_rotation(1, "L5", "L5").unwrap();
+        assert_eq!(format!("[{:<4}]", rotation), "[L5  ]");
+    }
+
+    #[test]
+    fn dials_of_any_size() {
+        let rotate = |dial: &mut Dial, direction, distance: u64| {
+            let event = dial.rotate(direction, &BigUint::from(distance));
+            (
+                event.end,
+                event.landed_on_zero,
+                event.zero_passes.to_u64().unwrap(),
+            )
+        };
+
+        // 3 -> 2 -> 1 -> 0 -> 6 -> ... -> 0 -> 6
+        let mut dial = Dial::new(7, 3).unwrap();
+        assert_eq!(rotate(&mut dial, Direction::Left, 11), (6, false, 2));
+        assert_eq!(rotate(&mut dial, Direction::Right, 8), (0, true, 2));
+        assert_eq!(rotate(&mut dial, Direction::Left, 7), (0, true, 1));
+
+        // A single-position dial points at 0 on every click
+        let mut dial = Dial::new(1, 0).unwrap();
+        assert_eq!(rotate(&mut dial, Direction::Right, 5), (0, true, 5));
+
+        assert_eq!(Dial::new(0, 0), Err(LockError::EmptyDial));
+        assert_eq!(
+            Dial::new(7, 7),
+            Err(LockError::StartOffDial {
+                modulus: 7,
+                start: 7
+            })
+        );
+    }
+
+    #[test]
+    fn histogram_counts_every_click() {
+        let mut histogram = PositionHistogram::new(5);
+
+        // 4 -> 0 -> 1, then 1 -> 0 -> 4 -> ... -> 1 -> 0 -> 4 -> 3
+        histogram.record(4, Direction::Right, &BigUint::from(2));
+        histogram.record(1, Direction::Left, &BigUint::from(8));
+
+        let visits: Vec<u64> = histogram
+            .visits()
+            .iter()
+            .map(|v| v.to_u64().unwrap())
+            .collect();
+        assert_eq!(visits, [3, 2, 1, 2, 2]);
+    }
+
+    #[test]
+    fn exports_zero_events_from_notes() {
+        // The edge cases from the notes in `Dial::rotate`: leaving 0 and landing exactly on 0
+        let exports = export("R50\nL17\nR12\nL95\n").unwrap();
+
+        assert_eq!(exports[1].rows, ["1,R50,1,true", "4,L95,1,true"]);
+    }
+
+    #[test]
+    fn exports_example_timeline() {
+        let exports = export(EXAMPLE).unwrap();
+        let [timeline, zero_events, positions] = exports.as_slice() else {
+            panic!("Expected a timeline, zero events and a histogram");
+        };
+
+        assert_eq!(timeline.rows.len(), 10);
+        assert_eq!(timeline.rows[0], "1,L68,50,82,1,false");
+        assert_eq!(timeline.rows[2], "3,R48,52,0,1,true");
+
+        assert_eq!(
+            zero_events.rows,
+            [
+                "1,L68,1,false",
+                "3,R48,1,true",
+                "5,R60,1,false",
+                "6,L55,1,true",
+                "8,L99,1,true",
+                "10,L82,1,false"
+            ]
+        );
+
+        // Every click is counted once, and the clicks on 0 are part 2's answer
+        let visits: Vec<u64> = positions
+            .rows
+            .iter()
+            .map(|row| row.split_once(',').unwrap().1.parse().unwrap())
+            .collect();
+        assert_eq!(visits.len(), 100);
+        assert_eq!(
+            visits.iter().sum::<u64>(),
+            68 + 30 + 48 + 5 + 60 + 55 + 1 + 99 + 14 + 82
+        );
+        assert_eq!(positions.rows[0], "0,6");
+    }
+
+    #[test]
+    fn lock_dials_rotate_independently() {
+        let steps = parse_lock_steps("A:R50\nB:L50\nA:R100\nB:R25\nB:L25\nC:L1\n").unwrap();
+        let mut lock = Lock::for_steps(&steps);
+        assert_eq!(lock.position("C"), Some(50));
+        assert_eq!(lock.position("D"), None);
+
+        let positions: Vec<[u64; 3]> = steps
+            .iter()
+            .map(|step| {
+                lock.rotate(step).unwrap();
+                ["A", "B", "C"].map(|name| lock.position(name).unwrap())
+            })
+            .collect();
+
+        assert_eq!(
+            positions,
+            [
+                [0, 50, 50],
+                [0, 0, 50],
+                [0, 0, 50],
+                [0, 25, 50],
+                [0, 0, 50],
+                [0, 0, 49]
+            ]
+        );
+    }
+
+    #[test]
+    fn lock_dials_of_different_sizes() {
+        // A: 7 -> 0 -> 7 -> 0, B: 0 -> 0 (a whole revolution)
+        let steps = parse_lock_steps("A:R3\nB:L4\nA:R7\nA:R3\n").unwrap();
+        let mut lock = Lock::new([
+            ("A", Dial::new(10, 7).unwrap()),
+            ("B", Dial::new(4, 0).unwrap()),
+        ]);
+
+        assert_eq!(lock.count_all_at_zero(&steps), Ok(3));
+        assert_eq!(lock.position("A"), Some(0));
+        assert_eq!(lock.position("B"), Some(0));
+
+        let unknown = LockStep {
+            dial: "C".to_string(),
+            rotation: steps[0].rotation.clone(),
+        };
+        assert_eq!(
+            lock.rotate(&unknown),
+            Err(LockError::UnknownDial("C".to_string()))
+        );
+    }
+
+    #[test]
+    fn lock_queries() {
+        let steps = parse_lock_steps("A:R50\nB:L50\nA:R100\nB:R25\nB:L25\n").unwrap();
+        let first_combination = |combination: &str| {
+            let combination = parse_combination(combination).unwrap();
+            Lock::for_steps(&steps).first_combination(&steps, &combination)
+        };
+
+        assert_eq!(Lock::for_steps(&steps).count_all_at_zero(&steps), Ok(3));
+        assert_eq!(first_combination("A=0,B=25"), Ok(Some(4)));
+        assert_eq!(first_combination("B=0"), Ok(Some(2)));
+        assert_eq!(first_combination("A=1"), Ok(None));
+
+        // The dials can already be at the combination before any steps
+        assert_eq!(first_combination("A=50,B=50"), Ok(Some(0)));
+
+        // Plain rotations on a single dial agree with part 1
+        let single: String = EXAMPLE.lines().map(|l| format!("A:{}\n", l)).collect();
+        let steps = parse_lock_steps(&single).unwrap();
+        assert_eq!(Lock::for_steps(&steps).count_all_at_zero(&steps), Ok(3));
+    }
+
+    #[test]
+    fn lock_rejects_combinations_that_cant_match() {
+        let steps = parse_lock_steps("A:R50\nB:L50\n").unwrap();
+        let mut lock = Lock::for_steps(&steps);
+
+        assert_eq!(
+            lock.first_combination(&steps, &[]),
+            Err(LockError::EmptyCombination)
+        );
+        assert_eq!(
+            lock.first_combination(&steps, &[("A", 0), ("C", 50)]),
+            Err(LockError::UnknownDial("C".to_string()))
+        );
+        assert_eq!(
+            lock.first_combination(&steps, &[("A", 0), ("B", 0), ("A", 50)]),
+            Err(LockError::DuplicateDial("A".to_string()))
+        );
+        assert_eq!(
+            lock.first_combination(&steps, &[("B", 100)]),
+            Err(LockError::PositionOffDial {
+                dial: "B".to_string(),
+                position: 100
+            })
+        );
+
+        // Nothing was rotated while checking
+        assert_eq!(lock.position("A"), Some(50));
+    }
+
+    #[test]
+    fn combination_errors() {
+        for (input, column) in [("A0", 1), ("A=0,=5", 5), ("A=x", 3), ("A=0,", 5)] {
+            let error = parse_combination(input).unwrap_err();
+            assert_eq!(error.column, column, "{}", input);
+        }
+    }
+
+    #[test]
+    fn dial_errors() {
+        assert_eq!(
+            parse_dial("A=10@3").unwrap(),
+            ("A", Dial::new(10, 3).unwrap())
+        );
+
+        for (input, column) in [
+            ("A", 1),
+            ("=10@3", 1),
+            ("A=10", 3),
+            ("A=x@3", 3),
+            ("A=10@10", 3),
+        ] {
+            let error = parse_dial(input).unwrap_err();
+            assert_eq!(error.column, column, "{}", input);
+        }
+    }
+
+    #[test]
+    fn lock_tool() {
+        let run = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+            run_lock("A:R3\nB:L4\nA:R7\nA:R3\n", &args)
+        };
+
+        // B is the puzzle's dial unless configured, so it never reaches 0 in 4 clicks
+        assert_eq!(
+            run(&["--dial", "A=10@7", "--target", "A=0,B=46"]),
+            Ok(vec![
+                "All dials at 0: 0 step(s)".to_string(),
+                "A=0,B=46: first reached at step 2".to_string()
+            ])
+        );
+        assert_eq!(
+            run(&["--dial", "A=10@7", "--dial", "B=4@0"]),
+            Ok(vec!["All dials at 0: 3 step(s)".to_string()])
+        );
+
+        assert!(matches!(
+            run(&["--dial", "A=0@0"]),
+            Err(ToolError::Usage(_))
+        ));
+        assert!(matches!(
+            run(&["--dial", "A=10@7", "--dial", "A=10@0"]),
+            Err(ToolError::Usage(_))
+        ));
+        assert!(matches!(run(&["--target"]), Err(ToolError::Usage(_))));
+        assert_eq!(
+            run(&["--target", "A=0,A=3"]),
+            Err(ToolError::Failed(
+                "Combination names dial 'A' more than once".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn lock_step_errors() {
+        for (input, column) in [("L39\n", 1), (":L39\n", 1), ("A:X39\n", 3), ("A:L-1\n", 4)] {
+            let error = parse_lock_steps(input).unwrap_err();
+            assert_eq!(error.column, column, "{}", input);
+        }
+    }
+
+    #[test]
+    fn huge_distances() {
+        // 50 clicks to reach 0 followed by 10^28 revolutions, then u64::MAX / 100 revolutions
+        let input = format!(
+            "R{}\nL{}\n",
+            "1".to_string() + &"0".repeat(28) + "50",
+            u64::MAX
+        );
+        let counts = count_zeroes(input.as_bytes(), Dial::default()).unwrap();
+
+        assert_eq!(counts.passes.to_string(), "10000000000184467440737095517");
+        assert_eq!(counts.landed, 1);
+
+        assert_eq!(Day1::solve_part_2(&input).unwrap(), counts.passes);
+        assert_eq!(Day1::solve_part_1(&input).unwrap(), counts.landed as usize);
+    }
 }
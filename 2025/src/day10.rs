@@ -1,27 +1,9 @@
-use std::{
-    collections::{HashMap, HashSet},
-    env, process,
-};
+use std::collections::{HashMap, HashSet};
 
-use advent_of_code_2025::*;
+use advent_of_code_common::{Input, Solution, error::ParseError, parallel, parser::*};
 use microlp::{ComparisonOp, OptimizationDirection, Problem, Variable};
-use regex::Regex;
 
-fn main() {
-    println!("----- Day 10 -----");
-
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("No input file specified");
-        process::exit(1);
-    }
-
-    let input_path = &args[1];
-    println!("Input File: {}", input_path);
-
-    println!("Part 1: {}", solve_part_1(input_path));
-    println!("Part 2: {}", solve_part_2(input_path));
-}
+pub struct Day10;
 
 #[derive(PartialEq, Eq, Hash, Debug)]
 struct LightState {
@@ -61,78 +43,138 @@ impl Button {
 }
 
 #[derive(Debug)]
-struct Machine {
+pub struct Machine {
     light_count: usize,
     target_state: LightState,
     buttons: Vec<Button>,
     required_joltage: Vec<u32>,
 }
 
-fn parse_machines(input_lines: &mut impl Iterator<Item = String>) -> Vec<Machine> {
-    let target_state_regex = Regex::new(r"\[([.#]+)\]").unwrap();
-    let button_regex = Regex::new(r"\([\d,]+\)").unwrap();
-    let required_joltage_regex = Regex::new(r"\{[\d,]+\}").unwrap();
-
-    let mut machines = Vec::new();
-
-    for line in input_lines {
-        let target_state_str = target_state_regex.find(&line).unwrap().as_str();
-        let target_state = parse_target_state(target_state_str);
+fn parse_machines<'a>(
+    input_lines: impl Iterator<Item = (usize, &'a str)>,
+) -> Result<Vec<Machine>, ParseError> {
+    input_lines
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_number, line)| parse_machine(line_number, line))
+        .collect()
+}
 
-        let buttons = button_regex
-            .find_iter(&line)
-            .map(|m| parse_button(m.as_str()))
-            .collect::<Vec<_>>();
+/// Parses a machine definition, e.g. `[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}`.
+fn parse_machine(line_number: usize, line: &str) -> Result<Machine, ParseError> {
+    let light = map(one_of(".#"), |c| c == '#');
+    let target_state = ws(spanned(bracketed("[", many1(light), "]")));
+
+    let light_indexes = separated_list1(spanned(integer::<usize>()), ",");
+    let buttons = many0(ws(bracketed("(", light_indexes, ")")));
+
+    let joltages = separated_list1(integer::<u32>(), ",");
+    let required_joltage = ws(spanned(bracketed("{", joltages, "}")));
+
+    let ((lights, lights_str), buttons, (required_joltage, required_joltage_str)) = parse_line(
+        line_number,
+        line,
+        &mut (target_state, buttons, required_joltage),
+    )?;
+
+    let light_count = lights.len();
+
+    let buttons = buttons
+        .into_iter()
+        .map(|light_indexes| {
+            let indexes = light_indexes
+                .into_iter()
+                .map(|(light_idx, light_idx_str)| {
+                    if light_idx >= light_count {
+                        return Err(ParseError::new(
+                            line_number,
+                            line,
+                            light_idx_str,
+                            "Button refers to a light that doesn't exist",
+                        ));
+                    }
+
+                    Ok(light_idx)
+                })
+                .collect::<Result<_, _>>()?;
+
+            Ok(Button { indexes })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if required_joltage.len() != light_count {
+        return Err(ParseError::new(
+            line_number,
+            line,
+            required_joltage_str,
+            format!("Expected {} joltage requirements", light_count),
+        ));
+    }
 
-        let required_joltage_str = required_joltage_regex.find(&line).unwrap().as_str();
-        let required_joltage = parse_required_joltage(required_joltage_str);
+    let machine = Machine {
+        light_count,
+        target_state: LightState { lights },
+        buttons,
+        required_joltage,
+    };
+
+    // Neither part can report an error, so machines without an answer are rejected up front
+    if !can_reach_lights(&machine) {
+        return Err(ParseError::new(
+            line_number,
+            line,
+            lights_str,
+            "No combination of buttons turns on these lights",
+        ));
+    }
 
-        machines.push(Machine {
-            light_count: target_state.lights.len(),
-            target_state,
-            buttons,
-            required_joltage,
-        });
+    if min_presses_for_joltage(&machine).is_none() {
+        return Err(ParseError::new(
+            line_number,
+            line,
+            required_joltage_str,
+            "No combination of buttons reaches these joltages",
+        ));
     }
 
-    machines
+    Ok(machine)
 }
 
-fn parse_target_state(input: &str) -> LightState {
-    let lights = input
-        .trim_start_matches("[")
-        .trim_end_matches("]")
-        .chars()
-        .map(|c| match c {
-            '.' => false,
-            '#' => true,
-            x => panic!("Unexpected light state {}", x),
-        })
-        .collect();
+/// Checks whether the target lights can be reached at all.
+///
+/// Pressing a button twice undoes it, so the reachable states are the XOR combinations of the
+/// buttons. Gaussian elimination over GF(2) checks the target is one of them, without searching.
+fn can_reach_lights(machine: &Machine) -> bool {
+    // Basis row for each pivot light, where the row has no lights on before its pivot
+    let mut basis: Vec<Option<Vec<bool>>> = vec![None; machine.light_count];
+
+    let reduce = |basis: &[Option<Vec<bool>>], lights: &mut [bool]| {
+        for pivot in 0..lights.len() {
+            if !lights[pivot] {
+                continue;
+            }
 
-    LightState { lights }
-}
+            match &basis[pivot] {
+                Some(row) => lights.iter_mut().zip(row).for_each(|(l, r)| *l ^= r),
+                None => return Some(pivot),
+            }
+        }
 
-fn parse_button(input: &str) -> Button {
-    let light_indexes = input
-        .trim_start_matches("(")
-        .trim_end_matches(")")
-        .split(",")
-        .filter_map(|idx_str| idx_str.parse::<usize>().ok())
-        .collect();
+        None
+    };
+
+    for button in &machine.buttons {
+        let mut lights = vec![false; machine.light_count];
+        for &idx in &button.indexes {
+            lights[idx] = true;
+        }
 
-    Button {
-        indexes: light_indexes,
+        if let Some(pivot) = reduce(&basis, &mut lights) {
+            basis[pivot] = Some(lights);
+        }
     }
-}
 
-fn parse_required_joltage(input: &str) -> Vec<u32> {
-    input
-        .trim_start_matches("{")
-        .trim_end_matches("}")
-        .split(",")
-        .filter_map(|joltage_str| joltage_str.parse::<u32>().ok())
-        .collect()
+    let mut target = machine.target_state.lights.clone();
+    reduce(&basis, &mut target).is_none()
 }
 
 fn calculate_min_presses_for_lights(machine: &Machine) -> usize {
@@ -151,11 +193,11 @@ fn calculate_min_presses_for_lights(machine: &Machine) -> usize {
         let mut starting_states = unexplored_states.drain().collect::<Vec<_>>();
         for button in &machine.buttons {
             for starting_state in &starting_states {
-                let derived_state = button.generate_state(&starting_state);
+                let derived_state = button.generate_state(starting_state);
 
                 if derived_state == machine.target_state {
                     return button_presses;
-                } else {
+                } else if !seen_states.contains(&derived_state) {
                     unexplored_states.insert(derived_state);
                 }
             }
@@ -164,10 +206,15 @@ fn calculate_min_presses_for_lights(machine: &Machine) -> usize {
         seen_states.extend(starting_states.drain(..));
     }
 
-    panic!("Could not find target state");
+    unreachable!("Target lights are checked when parsing");
 }
 
 fn calculate_min_presses_for_joltage(machine: &Machine) -> u32 {
+    min_presses_for_joltage(machine).expect("Joltages are checked when parsing")
+}
+
+/// Solves the joltage counters for a machine, or returns `None` if they can't be reached.
+fn min_presses_for_joltage(machine: &Machine) -> Option<u32> {
     /*
      * Technically, BFS will give the correct answer, but it's too slow and we'll run out of
      * memory... Intended way appears to be integer linear programming (ILP).
@@ -226,36 +273,88 @@ fn calculate_min_presses_for_joltage(machine: &Machine) -> u32 {
         lp_problem.add_constraint(linear_expr, ComparisonOp::Eq, target_joltage as f64);
     }
 
-    let solution = lp_problem
-        .solve()
-        .expect("Unable to solve LP problem for joltage counters");
+    let solution = lp_problem.solve().ok()?;
 
-    button_vars
-        .values()
-        .map(|v| solution.var_value_rounded(*v) as u32)
-        .sum()
+    Some(
+        button_vars
+            .values()
+            .map(|v| solution.var_value_rounded(*v) as u32)
+            .sum(),
+    )
 }
 
-/// Determines the minimum number of button presses for each machine
-fn solve_part_1(input_path: &str) -> usize {
-    let mut input_lines = read_lines(input_path);
-    let machines = parse_machines(&mut input_lines);
+impl Solution for Day10 {
+    type Input = Vec<Machine>;
+    type Answer1 = usize;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_machines(Input::new(input).lines())
+    }
+
+    /// Determines the minimum number of button presses for each machine
+    fn part1(machines: &Self::Input) -> Self::Answer1 {
+        let mut total_min_presses = 0;
 
-    let mut total_min_presses = 0;
+        for machine in machines {
+            total_min_presses += calculate_min_presses_for_lights(machine);
+        }
 
-    for machine in machines {
-        total_min_presses += calculate_min_presses_for_lights(&machine);
+        total_min_presses
     }
 
-    total_min_presses
+    fn part2(machines: &Self::Input) -> Self::Answer2 {
+        // Each machine is independent (and some take a while), so they're solved in parallel
+        parallel::map(machines, calculate_min_presses_for_joltage)
+            .into_iter()
+            .sum()
+    }
 }
 
-fn solve_part_2(input_path: &str) -> u32 {
-    let mut input_lines = read_lines(input_path);
-    let machines = parse_machines(&mut input_lines);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    machines
-        .iter()
-        .map(|m| calculate_min_presses_for_joltage(m))
-        .sum()
+    const EXAMPLE: &str = "\
+[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+";
+
+    /// Machine used in the joltage notes above.
+    const SINGLE_MACHINE: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}\n";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day10::solve_part_1(EXAMPLE).unwrap(), 7);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day10::solve_part_2(EXAMPLE).unwrap(), 33);
+    }
+
+    #[test]
+    fn single_machine_from_notes() {
+        assert_eq!(Day10::solve_part_1(SINGLE_MACHINE).unwrap(), 2);
+        assert_eq!(Day10::solve_part_2(SINGLE_MACHINE).unwrap(), 10);
+    }
+
+    #[test]
+    fn rejects_unreachable_lights() {
+        let error = Day10::parse("[#.] (1) {0,1}\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+
+        let error = Day10::parse("[.##.] (0,2) (0,3) {1,1,1,1}\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+
+        let error = Day10::parse("[#] {1}\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+    }
+
+    #[test]
+    fn rejects_unreachable_joltages() {
+        let error = Day10::parse("[##] (0,1) {1,2}\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 12));
+    }
 }
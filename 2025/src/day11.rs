@@ -1,144 +1,145 @@
-use std::{collections::HashMap, env, process};
+use std::collections::HashMap;
 
-use advent_of_code_2025::*;
+use advent_of_code_common::{
+    Input, Solution, bigint::BigUint, error::ParseError, graph::count_paths,
+};
 
-fn main() {
-    println!("----- Day 11 -----");
+pub struct Day11;
 
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("No input file specified");
-        process::exit(1);
-    }
-
-    let input_path = &args[1];
-    println!("Input File: {}", input_path);
-
-    println!("Part 1: {}", solve_part_1(input_path));
-    println!("Part 2: {}", solve_part_2(input_path));
-}
-
-const DAC_NODE: &'static str = "dac";
-const FFT_NODE: &'static str = "fft";
-const OUT_NODE: &'static str = "out";
-const SVR_NODE: &'static str = "svr";
-const YOU_NODE: &'static str = "you";
+pub(crate) const DAC_NODE: &str = "dac";
+pub(crate) const FFT_NODE: &str = "fft";
+pub(crate) const OUT_NODE: &str = "out";
+pub(crate) const SVR_NODE: &str = "svr";
+pub(crate) const YOU_NODE: &str = "you";
 
-fn parse_input(mut input_lines: impl Iterator<Item = String>) -> HashMap<String, Vec<String>> {
+fn parse_input(input: Input) -> Result<HashMap<String, Vec<String>>, ParseError> {
     let mut adjacency_list = HashMap::new();
-    while let Some(line) = input_lines.next().filter(|l| !l.is_empty()) {
-        let (node_str, connecting_str) = line.split_once(":").unwrap();
 
-        let node = node_str.trim().to_string();
+    // Only the first section contains devices
+    let device_lines = input.sections().next().map(|s| s.lines());
+
+    for (line_number, line) in device_lines.into_iter().flatten() {
+        let (node_str, connecting_str) = line.split_once(":").ok_or_else(|| {
+            ParseError::new(line_number, line, line, "Expected ':' after device name")
+        })?;
+
+        let node = node_str.trim();
+        if node.is_empty() {
+            return Err(ParseError::new(
+                line_number,
+                line,
+                node,
+                "Missing device name",
+            ));
+        }
+
         let connected_nodes = connecting_str
             .split_whitespace()
             .map(|n| n.trim().to_string())
             .collect::<Vec<_>>();
 
-        adjacency_list.insert(node, connected_nodes);
+        adjacency_list.insert(node.to_string(), connected_nodes);
     }
 
-    adjacency_list
+    Ok(adjacency_list)
 }
 
-fn count_paths(
-    start_node: &str,
-    end_node: &str,
-    adjacency_list: &HashMap<String, Vec<String>>,
-) -> u64 {
-    // Invert the graph, filtering to nodes that are relevant to search
-    let mut unexplored_nodes: Vec<&str> = vec![start_node];
-    let mut inverted_adjacency_list: HashMap<&str, Vec<&str>> = HashMap::new();
-
-    while !unexplored_nodes.is_empty() {
-        let nodes = unexplored_nodes.drain(..).collect::<Vec<_>>();
-
-        for node in nodes {
-            if let Some(connecting_nodes) = adjacency_list.get(node) {
-                for connecting_node in connecting_nodes.iter().map(|n| n.as_str()) {
-                    if let Some(parent_nodes) = inverted_adjacency_list.get_mut(connecting_node) {
-                        parent_nodes.push(node);
-                    } else {
-                        inverted_adjacency_list.insert(connecting_node, vec![node]);
-
-                        if connecting_node != end_node {
-                            unexplored_nodes.push(connecting_node);
-                        }
-                    }
-                }
-            }
-        }
+impl Solution for Day11 {
+    /// Adjacency list for each device's outputs.
+    type Input = HashMap<String, Vec<String>>;
+    type Answer1 = BigUint;
+    type Answer2 = BigUint;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(Input::new(input))
     }
 
-    // Then recursively count the total incoming paths for the end node
-    count_paths_inner(
-        start_node,
-        end_node,
-        &inverted_adjacency_list,
-        &mut HashMap::with_capacity(inverted_adjacency_list.len()),
-    )
-}
+    /// Counts how many paths there are from 'you' to 'out'.
+    fn part1(adjacency_list: &Self::Input) -> Self::Answer1 {
+        count_paths(YOU_NODE, OUT_NODE, adjacency_list)
+    }
 
-fn count_paths_inner<'a>(
-    root_node: &'a str,
-    node: &'a str,
-    inverted_adjacency_list: &HashMap<&'a str, Vec<&'a str>>,
-    memoized_counts: &mut HashMap<&'a str, u64>,
-) -> u64 {
-    if node == root_node {
-        // Base case (to allow recursion to complete)
-        1
-    } else if let Some(count) = memoized_counts.get(&node) {
-        // Already calculated for this node
-        *count
-    } else if let Some(parent_nodes) = inverted_adjacency_list.get(&node) {
-        // Sum the total from each parent node and cache value
-        let mut total = 0;
-
-        for parent_node in parent_nodes {
-            total += count_paths_inner(
-                root_node,
-                parent_node,
-                inverted_adjacency_list,
-                memoized_counts,
-            );
+    /// Counts how many paths there are from 'svr' to 'out' which include 'dac' and 'fft'.
+    fn part2(adjacency_list: &Self::Input) -> Self::Answer2 {
+        // Input guarantees that there are no loops in the graph
+        // This means that when both dac and fft are visited, the order they're reached is always the same
+        let path_count_dac_fft = count_paths(DAC_NODE, FFT_NODE, adjacency_list);
+        if !path_count_dac_fft.is_zero() {
+            // Paths are of form svr -> ... -> dac -> ... -> fft -> ... -> out
+            let path_count_start_dac = count_paths(SVR_NODE, DAC_NODE, adjacency_list);
+            let path_count_fft_end = count_paths(FFT_NODE, OUT_NODE, adjacency_list);
+
+            path_count_start_dac * path_count_dac_fft * path_count_fft_end
+        } else {
+            // Paths are of form svr -> ... -> fft -> ... -> dac -> ... -> out
+            let path_count_start_fft = count_paths(SVR_NODE, FFT_NODE, adjacency_list);
+            let path_count_fft_dac = count_paths(FFT_NODE, DAC_NODE, adjacency_list);
+            let path_count_dac_end = count_paths(DAC_NODE, OUT_NODE, adjacency_list);
+
+            path_count_start_fft * path_count_fft_dac * path_count_dac_end
         }
-
-        memoized_counts.insert(node, total);
-        total
-    } else {
-        0
     }
 }
 
-/// Counts how many paths there are from 'you' to 'out'.
-fn solve_part_1(input_path: &str) -> u64 {
-    let input_lines = read_lines(input_path);
-    let adjacency_list = parse_input(input_lines);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_PART_1: &str = "\
+aaa: you hhh
+you: bbb ccc
+bbb: ddd eee
+ccc: ddd eee fff
+ddd: ggg
+eee: out
+fff: out
+ggg: out
+hhh: ccc fff iii
+iii: out
+";
+
+    const EXAMPLE_PART_2: &str = "\
+svr: aaa bbb
+aaa: fft
+fft: ccc
+bbb: tty
+tty: ccc
+ccc: ddd eee
+ddd: hub
+hub: fff
+eee: dac
+dac: fff
+fff: ggg hhh
+ggg: out
+hhh: out
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day11::solve_part_1(EXAMPLE_PART_1).unwrap(), 5);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day11::solve_part_2(EXAMPLE_PART_2).unwrap(), 2);
+    }
 
-    count_paths(YOU_NODE, OUT_NODE, &adjacency_list)
-}
+    #[test]
+    fn path_counts_past_u64() {
+        // Chain of 70 diamonds, each of which doubles the number of paths
+        let mut input = String::from("you: a0 b0\n");
+        for i in 0..70 {
+            let next = if i == 69 {
+                "out".to_string()
+            } else {
+                format!("n{}", i)
+            };
+            input += &format!("a{i}: {next}\nb{i}: {next}\nn{i}: a{} b{}\n", i + 1, i + 1);
+        }
 
-/// Counts how many paths there are from 'svr' to 'out' which include 'dac' and 'fft'.
-fn solve_part_2(input_path: &str) -> u64 {
-    let input_lines = read_lines(input_path);
-    let adjacency_list = parse_input(input_lines);
-
-    // Input guarantees that there are no loops in the graph
-    // This means that when both dac and fft are visited, the order they're reached is always the same
-    let path_count_dac_fft = count_paths(DAC_NODE, FFT_NODE, &adjacency_list);
-    if path_count_dac_fft > 0 {
-        // Paths are of form svr -> ... -> dac -> ... -> fft -> ... -> out
-        let path_count_start_dac = count_paths(SVR_NODE, DAC_NODE, &adjacency_list);
-        let path_count_fft_end = count_paths(FFT_NODE, OUT_NODE, &adjacency_list);
-
-        path_count_start_dac * path_count_dac_fft * path_count_fft_end
-    } else {
-        // Paths are of form svr -> ... -> fft -> ... -> dac -> ... -> out
-        let path_count_start_fft = count_paths(SVR_NODE, FFT_NODE, &adjacency_list);
-        let path_count_fft_dac = count_paths(FFT_NODE, DAC_NODE, &adjacency_list);
-        let path_count_dac_end = count_paths(DAC_NODE, OUT_NODE, &adjacency_list);
-
-        path_count_start_fft * path_count_fft_dac * path_count_dac_end
+        assert_eq!(
+            Day11::solve_part_1(&input).unwrap().to_string(),
+            "1180591620717411303424"
+        );
     }
 }
@@ -1,32 +1,15 @@
-use std::{env, process};
+use advent_of_code_common::{Input, Solution, error::ParseError, grid::Grid, parallel, parser::*};
 
-use advent_of_code_2025::*;
-
-fn main() {
-    println!("----- Day 12 -----");
-
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("No input file specified");
-        process::exit(1);
-    }
-
-    let input_path = &args[1];
-    println!("Input File: {}", input_path);
-
-    println!("Part 1: {}", solve(input_path));
-}
+pub struct Day12;
 
 #[allow(unused)]
 #[derive(Clone, Debug)]
-struct Present {
-    cells: Vec<Vec<bool>>,
-    width: usize,
-    height: usize,
+pub struct Present {
+    cells: Grid<bool>,
 }
 
 #[derive(Clone, Debug)]
-struct XmasTree {
+pub struct XmasTree {
     region_width: usize,
     region_height: usize,
     present_counts: Vec<usize>,
@@ -43,20 +26,23 @@ impl XmasTree {
     }
 }
 
-fn parse_input(mut input_lines: impl Iterator<Item = String>) -> (Vec<Present>, Vec<XmasTree>) {
+fn parse_input(input: Input) -> Result<(Vec<Present>, Vec<XmasTree>), ParseError> {
     let mut presents = Vec::new();
     let mut xmas_trees = Vec::new();
 
-    while let Some(input_line) = input_lines.next() {
-        if input_line.is_empty() {
+    for section in input.sections() {
+        let mut section_lines = section.lines();
+        let Some((header_line_number, header_line)) = section_lines.next() else {
             continue;
-        }
+        };
 
-        if input_line.contains("x") {
-            // Tree definition, e.g.:
+        if header_line.contains("x") {
+            // Tree definitions, e.g.:
             //
             // 12x10: 0 1 2 13
-            xmas_trees.push(parse_tree_definition(&input_line));
+            for (line_number, input_line) in section.lines() {
+                xmas_trees.push(parse_tree_definition(line_number, input_line)?);
+            }
         } else {
             // Present definition, e.g.:
             //
@@ -64,74 +50,151 @@ fn parse_input(mut input_lines: impl Iterator<Item = String>) -> (Vec<Present>,
             // ###
             // ##.
             // ##.
-            presents.push(parse_present_definition(&mut input_lines));
+            presents.push(parse_present_definition(
+                header_line_number,
+                header_line,
+                section_lines,
+            )?);
         }
     }
 
-    (presents, xmas_trees)
+    Ok((presents, xmas_trees))
 }
 
-fn parse_present_definition(input_lines: &mut impl Iterator<Item = String>) -> Present {
-    // When this function is called, we'll be on the first line of the cells definition
-    // Keep parsing until newline is encountered
-    let mut cells: Vec<Vec<bool>> = Vec::new();
-
-    while let Some(input_line) = input_lines.next().filter(|l| !l.is_empty()) {
-        let row = input_line
-            .trim()
-            .chars()
-            .filter_map(|c| match c {
-                '#' => Some(true),
-                '.' => Some(false),
-                _ => None,
-            })
-            .collect();
-
-        cells.push(row);
+fn parse_present_definition<'a>(
+    header_line_number: usize,
+    header_line: &str,
+    cell_lines: impl Iterator<Item = (usize, &'a str)>,
+) -> Result<Present, ParseError> {
+    let cells = Grid::parse_lines(cell_lines)?;
+    if cells.is_empty() {
+        return Err(ParseError::new(
+            header_line_number,
+            header_line,
+            header_line,
+            "Expected present shape after",
+        ));
     }
 
-    let height = cells.len();
-    let width = cells[0].len();
-
-    Present {
-        cells,
-        width,
-        height,
-    }
+    Ok(Present { cells })
 }
 
-fn parse_tree_definition(input_line: &str) -> XmasTree {
-    // Split by ':' first
-    let (region_str, present_counts_str) = input_line.trim().split_once(":").unwrap();
-    let (region_width_str, region_height_str) = region_str.split_once("x").unwrap();
+/// Parses a tree definition, e.g. `12x10: 0 1 2 13`.
+fn parse_tree_definition(line_number: usize, input_line: &str) -> Result<XmasTree, ParseError> {
+    let region_size = (
+        ws(integer::<usize>()),
+        literal("x"),
+        integer::<usize>(),
+        literal(":"),
+    );
+    let present_counts = many0(ws(integer::<usize>()));
 
-    let region_width = region_width_str.parse::<usize>().unwrap();
-    let region_height = region_height_str.parse::<usize>().unwrap();
+    let ((region_width, _, region_height, _), present_counts) =
+        parse_line(line_number, input_line, &mut (region_size, present_counts))?;
 
-    let present_counts = present_counts_str
-        .split_whitespace()
-        .filter_map(|c| c.trim().parse::<usize>().ok())
-        .collect::<Vec<_>>();
-
-    XmasTree {
+    Ok(XmasTree {
         region_width,
         region_height,
         present_counts,
+    })
+}
+
+impl Solution for Day12 {
+    type Input = (Vec<Present>, Vec<XmasTree>);
+    type Answer1 = usize;
+    type Answer2 = &'static str;
+
+    const PARTS: usize = 1;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(Input::new(input))
+    }
+
+    /// "Calculates" how many of the christmas trees can fit the required presents.
+    ///
+    /// This only works on the real input as it assumes:
+    ///
+    /// - All presents are 3x3
+    /// - Presents don't overlap when placing them down
+    fn part1((_, xmas_trees): &Self::Input) -> Self::Answer1 {
+        parallel::map(xmas_trees, XmasTree::can_fit_all_3x3_presents)
+            .into_iter()
+            .filter(|&fits| fits)
+            .count()
+    }
+
+    /// There's no part 2 on the last day.
+    fn part2(_: &Self::Input) -> Self::Answer2 {
+        "N/A"
     }
 }
 
-/// "Calculates" how many of the christmas trees can fit the required presents.
-///
-/// This only works on the real input as it assumes:
-///
-/// - All presents are 3x3
-/// - Presents don't overlap when placing them down
-fn solve(input_path: &str) -> usize {
-    let input_lines = read_lines(input_path);
-    let (_, xmas_trees) = parse_input(input_lines);
-
-    xmas_trees
-        .iter()
-        .filter(|tree| tree.can_fit_all_3x3_presents())
-        .count()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+0:
+###
+##.
+##.
+
+1:
+###
+##.
+.##
+
+2:
+.##
+###
+##.
+
+3:
+##.
+###
+##.
+
+4:
+###
+#..
+###
+
+5:
+###
+.#.
+###
+
+4x4: 0 0 0 0 2 0
+12x5: 1 0 1 0 2 2
+12x5: 1 0 1 0 3 2
+";
+
+    #[test]
+    fn parses_example() {
+        let (presents, xmas_trees) = Day12::parse(EXAMPLE).unwrap();
+
+        assert_eq!(presents.len(), 6);
+        assert!(
+            presents
+                .iter()
+                .all(|p| p.cells.rows() == 3 && p.cells.cols() == 3)
+        );
+        assert_eq!(xmas_trees.len(), 3);
+        assert_eq!(xmas_trees[1].present_counts, vec![1, 0, 1, 0, 2, 2]);
+    }
+
+    #[test]
+    fn part1_example() {
+        // The puzzle's answer for the example is 2, but the example relies on presents
+        // overlapping, which the 3x3 assumption doesn't handle
+        assert_eq!(Day12::solve_part_1(EXAMPLE).unwrap(), 0);
+    }
+
+    #[test]
+    fn part1_fits_non_overlapping_presents() {
+        assert_eq!(
+            Day12::solve_part_1("0:\n###\n\n6x6: 4\n6x6: 5\n").unwrap(),
+            1
+        );
+    }
 }
@@ -1,158 +1,161 @@
-use std::{
-    collections::HashSet,
-    env,
-    fs::File,
-    io::{BufRead, BufReader},
-    process,
-};
-
-fn main() {
-    println!("----- Day 2 -----");
+use std::collections::HashSet;
 
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("No input file specified");
-        process::exit(1);
-    }
+use advent_of_code_common::{
+    Solution, bigint::BigUint, debug, error::ParseError, parser::*, trace,
+};
 
-    let input_path = &args[1];
-    println!("Input File: {}", input_path);
+pub struct Day2;
 
-    println!("Part 1: {}", solve_part_1(input_path));
-    println!("Part 2: {}", solve_part_2(input_path));
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct IdRange {
+    start: String,
+    end: String,
 }
 
-fn read_file_contents(path: &str) -> String {
-    let file = File::open(path).expect("Unable to open input file");
-    let mut input_reader = BufReader::new(file);
+/// Parses the comma-separated ID ranges on the first line, e.g. `11-22,95-115`.
+fn parse_id_ranges(input: &str) -> Result<Vec<IdRange>, ParseError> {
+    let line = input.lines().next().unwrap_or_default();
 
-    let mut input_str = String::new();
-    input_reader
-        .read_line(&mut input_str)
-        .expect("No input lines found");
+    // IDs are handled as strings, but still need to be valid numbers
+    // Limiting them to u64 means the prefixes (at most half the digits) always fit in a u64 too
+    let id = || map(spanned(integer::<u64>()), |(_, id_str)| id_str.to_string());
+    let id_range = map((ws(id()), literal("-"), id()), |(start, _, end)| IdRange {
+        start,
+        end,
+    });
 
-    input_str
-}
+    // Ranges need a comma between them, and can have one after the last range too
+    let mut id_ranges = opt(separated_list1_trailing(id_range, ","));
 
-fn parse_id_ranges<'a>(input: &'a str) -> impl Iterator<Item = (&'a str, &'a str)> {
-    input
-        .trim()
-        .split(",")
-        .filter(|s| !s.is_empty())
-        .filter_map(|s| s.split_once("-"))
+    Ok(parse_line(1, line, &mut id_ranges)?.unwrap_or_default())
 }
 
-/// Determines which IDs contain 2 repeated halves, e.g. 113113, then sums them together.
-fn solve_part_1(input_path: &str) -> u64 {
-    let input = read_file_contents(input_path);
-    let id_ranges_iter = parse_id_ranges(&input);
-
-    let mut total = 0;
+impl Solution for Day2 {
+    type Input = Vec<IdRange>;
+    type Answer1 = BigUint;
+    type Answer2 = BigUint;
 
-    for (range_start_str, range_end_str) in id_ranges_iter {
-        // println!("{}-{}", range_start_str, range_end_str);
-
-        // Determine where to start looking for invalid IDs
-        let start_digits = range_start_str.chars().count();
-        let start_middle_digit = start_digits / 2;
-
-        let invalid_start = if start_digits % 2 == 0 {
-            // Even Case: Split at middle digit
-            // - If left >= right, start at left, e.g. 1402 => start at 14
-            // - Otherwise, start at left + 1, e.g. 1425 => start at 15
-            let (left, right) = range_start_str.split_at(start_middle_digit);
-            let left_parsed = left.parse::<u32>().unwrap();
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_id_ranges(input)
+    }
 
-            if left >= right {
-                left_parsed
+    /// Determines which IDs contain 2 repeated halves, e.g. 113113, then sums them together.
+    fn part1(id_ranges: &Self::Input) -> Self::Answer1 {
+        let mut total = BigUint::zero();
+
+        for IdRange {
+            start: range_start_str,
+            end: range_end_str,
+        } in id_ranges
+        {
+            debug!("{}-{}", range_start_str, range_end_str);
+
+            // Determine where to start looking for invalid IDs
+            let start_digits = range_start_str.chars().count();
+            let start_middle_digit = start_digits / 2;
+
+            let invalid_start = if start_digits % 2 == 0 {
+                // Even Case: Split at middle digit
+                // - If left >= right, start at left, e.g. 1402 => start at 14
+                // - Otherwise, start at left + 1, e.g. 1425 => start at 15
+                let (left, right) = range_start_str.split_at(start_middle_digit);
+                let left_parsed = left.parse::<u64>().unwrap();
+
+                if left >= right {
+                    left_parsed
+                } else {
+                    left_parsed + 1
+                }
             } else {
-                left_parsed + 1
-            }
-        } else {
-            // Odd Case: Start at 10 ^ (middle digit index)
-            // e.g. 12345 => start at 100
-            10_u32.pow(start_middle_digit as u32)
-        };
-
-        // Determine where to stop looking for invalid IDs
-        let end_digits = range_end_str.chars().count();
-        let end_middle_digit = end_digits / 2;
-
-        let invalid_end = if end_digits % 2 == 0 {
-            // Even Case: Split at middle digit
-            // - If left <= right, end at left, e.g. 1470 => end at 14
-            // - Otherwise, end at left - 1,    e.g. 1402 => end at 13
-            let (left, right) = range_end_str.split_at(end_middle_digit);
-            let left_parsed = left.parse::<u32>().unwrap();
-
-            if left <= right {
-                left_parsed
+                // Odd Case: Start at 10 ^ (middle digit index)
+                // e.g. 12345 => start at 100
+                10_u64.pow(start_middle_digit as u32)
+            };
+
+            // Determine where to stop looking for invalid IDs
+            let end_digits = range_end_str.chars().count();
+            let end_middle_digit = end_digits / 2;
+
+            let invalid_end = if end_digits % 2 == 0 {
+                // Even Case: Split at middle digit
+                // - If left <= right, end at left, e.g. 1470 => end at 14
+                // - Otherwise, end at left - 1,    e.g. 1402 => end at 13
+                let (left, right) = range_end_str.split_at(end_middle_digit);
+                let left_parsed = left.parse::<u64>().unwrap();
+
+                if left <= right {
+                    left_parsed
+                } else {
+                    left_parsed - 1
+                }
             } else {
-                left_parsed - 1
-            }
-        } else {
-            // Odd Case: Start at 10 ^ (middle digit index) - 1
-            // e.g. 12345 => end at 99
-            10_u32.pow(end_middle_digit as u32) - 1
-        };
+                // Odd Case: Start at 10 ^ (middle digit index) - 1
+                // e.g. 12345 => end at 99
+                10_u64.pow(end_middle_digit as u32) - 1
+            };
 
-        for i in invalid_start..=invalid_end {
-            // NOTE: Can definitely do this without string parsing, too lazy LOL
-            let invalid_id = format!("{}{}", i, i).parse::<u64>().unwrap();
-            // println!("- Invalid: {}", invalid_id);
+            for i in invalid_start..=invalid_end {
+                // NOTE: Can definitely do this without string parsing, too lazy LOL
+                let invalid_id = format!("{}{}", i, i).parse::<u64>().unwrap();
+                trace!("- Invalid: {}", invalid_id);
 
-            total += invalid_id;
+                total += BigUint::from(invalid_id);
+            }
         }
-    }
 
-    total
-}
-
-/// More generalised version of part 1, where it looks at repeating chunks of different sizes.
-fn solve_part_2(input_path: &str) -> u64 {
-    let input = read_file_contents(input_path);
-    let id_ranges_iter = parse_id_ranges(&input);
-
-    // Keep track of unique IDs, as duplicates can be encountered
-    // e.g. 2222 is encountered when chunk counts are:
-    // - 4: 2, 2, 2, 2
-    // - 2: 22, 22
-    let mut invalid_ids: HashSet<u64> = HashSet::new();
-
-    /*
-     * Looking back at my solution, I think it became complicated because I iterated over chunk
-     * count instead of chunk size. But it worked in the end, so ¯\_(ツ)_/¯
-     */
-    for (range_start_str, range_end_str) in id_ranges_iter {
-        let range_start = range_start_str.parse::<u64>().unwrap();
-        let range_end = range_end_str.parse::<u64>().unwrap();
-
-        // println!("{}-{}", range_start_str, range_end_str);
-        let max_chunk_count = std::cmp::max(range_start_str.len(), range_end_str.len());
-
-        for chunk_count in 2..=max_chunk_count {
-            // println!("- Chunk Count: {}", chunk_count);
-
-            let invalid_start = get_starting_number(range_start_str, chunk_count);
-            let invalid_end = get_ending_number(range_end_str, chunk_count);
-
-            for i in invalid_start..=invalid_end {
-                let invalid_id_str = i.to_string().repeat(chunk_count);
-                let invalid_id = invalid_id_str.parse::<u64>().unwrap();
+        total
+    }
 
-                if invalid_id >= range_start && invalid_id <= range_end {
-                    // println!("  - Invalid: {}", invalid_id);
-                    invalid_ids.insert(invalid_id);
+    /// More generalised version of part 1, where it looks at repeating chunks of different sizes.
+    fn part2(id_ranges: &Self::Input) -> Self::Answer2 {
+        // Keep track of unique IDs, as duplicates can be encountered
+        // e.g. 2222 is encountered when chunk counts are:
+        // - 4: 2, 2, 2, 2
+        // - 2: 22, 22
+        let mut invalid_ids: HashSet<u64> = HashSet::new();
+
+        /*
+         * Looking back at my solution, I think it became complicated because I iterated over chunk
+         * count instead of chunk size. But it worked in the end, so ¯\_(ツ)_/¯
+         */
+        for IdRange {
+            start: range_start_str,
+            end: range_end_str,
+        } in id_ranges
+        {
+            let range_start = range_start_str.parse::<u64>().unwrap();
+            let range_end = range_end_str.parse::<u64>().unwrap();
+
+            debug!("{}-{}", range_start_str, range_end_str);
+            let max_chunk_count = std::cmp::max(range_start_str.len(), range_end_str.len());
+
+            for chunk_count in 2..=max_chunk_count {
+                trace!("- Chunk Count: {}", chunk_count);
+
+                let invalid_start = get_starting_number(range_start_str, chunk_count);
+                let invalid_end = get_ending_number(range_end_str, chunk_count);
+
+                for i in invalid_start..=invalid_end {
+                    let invalid_id_str = i.to_string().repeat(chunk_count);
+                    // Anything too big for a u64 is past the end of the range anyway
+                    let Ok(invalid_id) = invalid_id_str.parse::<u64>() else {
+                        break;
+                    };
+
+                    if invalid_id >= range_start && invalid_id <= range_end {
+                        trace!("  - Invalid: {}", invalid_id);
+                        invalid_ids.insert(invalid_id);
+                    }
                 }
             }
         }
-    }
 
-    invalid_ids.iter().sum()
+        invalid_ids.into_iter().map(BigUint::from).sum()
+    }
 }
 
 /// More generalised way of getting the starting number to check.
-fn get_starting_number(range_start: &str, chunk_count: usize) -> u32 {
+fn get_starting_number(range_start: &str, chunk_count: usize) -> u64 {
     /*
      * Examples:
      * - 12345678, Chunk Count 2
@@ -168,14 +171,14 @@ fn get_starting_number(range_start: &str, chunk_count: usize) -> u32 {
     let digit_count = range_start.chars().count();
     let cutoff_idx = digit_count / chunk_count;
 
-    if digit_count % chunk_count == 0 {
+    if digit_count.is_multiple_of(chunk_count) {
         // Exact Split Case
         // - If 1st chunk >= 2nd chunk, start at 1st chunk
         // - Otherwise, start at 1st chunk + 1
         let chunk_1 = &range_start[0..cutoff_idx];
         let chunk_2 = &range_start[cutoff_idx..(cutoff_idx * 2)];
 
-        let chunk_1_parsed = chunk_1.parse::<u32>().unwrap();
+        let chunk_1_parsed = chunk_1.parse::<u64>().unwrap();
         if chunk_1 >= chunk_2 {
             chunk_1_parsed
         } else {
@@ -184,12 +187,12 @@ fn get_starting_number(range_start: &str, chunk_count: usize) -> u32 {
     } else {
         // Non-Exact Split Case
         // Start at 10 ^ Cutoff
-        10_u32.pow(cutoff_idx as u32)
+        10_u64.pow(cutoff_idx as u32)
     }
 }
 
 /// More generalised way of getting the ending number to check.
-fn get_ending_number(range_end: &str, chunk_count: usize) -> u32 {
+fn get_ending_number(range_end: &str, chunk_count: usize) -> u64 {
     /*
      * Examples:
      * - 12345678, Chunk Count 2
@@ -205,14 +208,14 @@ fn get_ending_number(range_end: &str, chunk_count: usize) -> u32 {
     let digit_count = range_end.chars().count();
     let cutoff_idx = digit_count / chunk_count;
 
-    if digit_count % chunk_count == 0 {
+    if digit_count.is_multiple_of(chunk_count) {
         // Exact Split Case
         // - If 1st chunk <= 2nd chunk, end at 1st chunk
         // - Otherwise, end at 1st chunk - 1
         let chunk_1 = &range_end[0..cutoff_idx];
         let chunk_2 = &range_end[cutoff_idx..(cutoff_idx * 2)];
 
-        let chunk_1_parsed = chunk_1.parse::<u32>().unwrap();
+        let chunk_1_parsed = chunk_1.parse::<u64>().unwrap();
         if chunk_1 <= chunk_2 {
             chunk_1_parsed
         } else {
@@ -221,6 +224,50 @@ fn get_ending_number(range_end: &str, chunk_count: usize) -> u32 {
     } else {
         // Non-Exact Split Case
         // End at 10 ^ Cutoff - 1
-        10_u32.pow(cutoff_idx as u32) - 1
+        10_u64.pow(cutoff_idx as u32) - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\
+1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
+824824821-824824827,2121212118-2121212124\n";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day2::solve_part_1(EXAMPLE).unwrap(), 1227775554);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day2::solve_part_2(EXAMPLE).unwrap(), 4174379265);
+    }
+
+    #[test]
+    fn ranges_need_separating_commas() {
+        assert_eq!(Day2::parse("11-22,33-44,\n").unwrap().len(), 2);
+
+        for (input, column) in [("11-22 33-44", 7), ("11-2233-44", 8), ("11-22,,33-44", 7)] {
+            let error = Day2::parse(input).unwrap_err();
+            assert_eq!(error.column, column, "{}", input);
+        }
+    }
+
+    #[test]
+    fn sums_past_u64() {
+        let input = "18446744061844674406-18446744071844674407,\
+18446744061844674406-18446744071844674407";
+
+        assert_eq!(
+            Day2::solve_part_1(input).unwrap().to_string(),
+            "73786976267378697626"
+        );
+        assert_eq!(
+            Day2::solve_part_2(input).unwrap().to_string(),
+            "36893488133689348813"
+        );
     }
 }
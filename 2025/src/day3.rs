@@ -1,119 +1,136 @@
-use std::{
-    env,
-    fs::File,
-    io::{BufRead, BufReader},
-    process,
-};
-
-fn main() {
-    println!("----- Day 3 -----");
-
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("No input file specified");
-        process::exit(1);
-    }
-
-    let input_path = &args[1];
-    println!("Input File: {}", input_path);
-
-    println!("Part 1: {}", solve_part_1(input_path));
-    println!("Part 2: {}", solve_part_2(input_path));
-}
-
-fn read_non_empty_lines(path: &str) -> impl Iterator<Item = String> {
-    let file = File::open(path).expect("Unable to open input file");
-    let reader = BufReader::new(file);
-
-    reader
-        .lines()
-        .filter_map(|result| result.ok().filter(|line| !line.is_empty()))
-}
+use advent_of_code_common::{Solution, error::ParseError, parallel, trace};
 
-/// Finds the largest possible joltage from each bank when only two batteries are enabled.
-fn solve_part_1(input_path: &str) -> u32 {
-    let input_lines = read_non_empty_lines(input_path);
-    let mut total = 0;
+pub struct Day3;
 
-    for input_line in input_lines {
-        let digits = input_line
-            .chars()
-            .map(|c| c.to_digit(10).unwrap())
-            .collect::<Vec<_>>();
+/// Number of batteries enabled in each bank for part 2.
+pub(crate) const BATTERY_COUNT: usize = 12;
 
-        let n = digits.len();
-
-        // Find lowest index x of largest digit between [0, n-1]
-        let (x, first_digit) = digits
-            .iter()
-            .enumerate()
-            .take(n - 1)
-            .min_by(|(idx1, digit1), (idx2, digit2)| {
-                digit1.cmp(digit2).reverse().then(idx1.cmp(idx2))
+fn parse_bank(line_number: usize, line: &str) -> Result<Vec<u32>, ParseError> {
+    let digits = line
+        .char_indices()
+        .map(|(idx, c)| {
+            c.to_digit(10).ok_or_else(|| {
+                let token = &line[idx..(idx + c.len_utf8())];
+                ParseError::new(line_number, line, token, "Expected joltage digit")
             })
-            .unwrap();
-
-        // Find largest digit between [x+1, n]
-        let second_digit = digits.iter().skip(x + 1).max().unwrap();
-
-        let max_bank_joltage = (*first_digit * 10) + *second_digit;
-        // println!(
-        //     "{} -> {}{} ({})",
-        //     input_line, *first_digit, *second_digit, max_bank_joltage
-        // );
-
-        total += max_bank_joltage;
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if digits.len() < BATTERY_COUNT {
+        return Err(ParseError::new(
+            line_number,
+            line,
+            line,
+            format!("Expected at least {} batteries in bank", BATTERY_COUNT),
+        ));
     }
 
-    total
+    Ok(digits)
 }
 
-/// Finds the largest possible joltage from each bank when 12 batteries are enabled.
-fn solve_part_2(input_path: &str) -> u64 {
-    let input_lines = read_non_empty_lines(input_path);
-    let mut total = 0;
-
-    const BATTERY_COUNT: usize = 12;
+impl Solution for Day3 {
+    /// Battery banks, where each battery is represented by its joltage digit.
+    type Input = Vec<Vec<u32>>;
+    type Answer1 = u32;
+    type Answer2 = u64;
 
-    for input_line in input_lines {
-        let digits = input_line
-            .chars()
-            .map(|c| c.to_digit(10).unwrap() as u64)
-            .collect::<Vec<_>>();
-
-        let n = digits.len();
-
-        let mut battery_joltage = 0;
-        let mut previous_idx: Option<usize> = None;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(line_number, line)| parse_bank(line_number, line))
+            .collect()
+    }
 
-        for i in (0..BATTERY_COUNT).rev() {
-            // If first selection, start at 0, otherwise prev + 1
-            // Then leave enough space at the end for subsequent batteries
-            let start_idx = previous_idx.map(|idx| idx + 1).unwrap_or(0);
-            let end_idx = n - i;
+    /// Finds the largest possible joltage from each bank when only two batteries are enabled.
+    fn part1(banks: &Self::Input) -> Self::Answer1 {
+        // Each bank is independent, so they're solved in parallel
+        let bank_joltages = parallel::map(banks, |digits| {
+            let n = digits.len();
 
-            // Find lowest index of largest digit between [start, end]
-            let (idx, digit) = digits
+            // Find lowest index x of largest digit between [0, n-1]
+            let (x, first_digit) = digits
                 .iter()
                 .enumerate()
-                .take(end_idx)
-                .skip(start_idx)
+                .take(n - 1)
                 .min_by(|(idx1, digit1), (idx2, digit2)| {
                     digit1.cmp(digit2).reverse().then(idx1.cmp(idx2))
                 })
                 .unwrap();
 
-            previous_idx = Some(idx);
-            battery_joltage = (battery_joltage * 10) + *digit;
-        }
+            // Find largest digit between [x+1, n]
+            let second_digit = digits.iter().skip(x + 1).max().unwrap();
 
-        // println!(
-        //     "{} -> {}",
-        //     input_line, battery_joltage
-        // );
+            let max_bank_joltage = (*first_digit * 10) + *second_digit;
+            trace!(
+                "{:?} -> {}{} ({})",
+                digits, *first_digit, *second_digit, max_bank_joltage
+            );
 
-        total += battery_joltage;
+            max_bank_joltage
+        });
+
+        bank_joltages.into_iter().sum()
     }
 
-    total
+    /// Finds the largest possible joltage from each bank when 12 batteries are enabled.
+    fn part2(banks: &Self::Input) -> Self::Answer2 {
+        let bank_joltages = parallel::map(banks, |digits| {
+            let n = digits.len();
+
+            let mut battery_joltage = 0;
+            let mut previous_idx: Option<usize> = None;
+
+            for i in (0..BATTERY_COUNT).rev() {
+                // If first selection, start at 0, otherwise prev + 1
+                // Then leave enough space at the end for subsequent batteries
+                let start_idx = previous_idx.map(|idx| idx + 1).unwrap_or(0);
+                let end_idx = n - i;
+
+                // Find lowest index of largest digit between [start, end]
+                let (idx, digit) = digits
+                    .iter()
+                    .enumerate()
+                    .take(end_idx)
+                    .skip(start_idx)
+                    .min_by(|(idx1, digit1), (idx2, digit2)| {
+                        digit1.cmp(digit2).reverse().then(idx1.cmp(idx2))
+                    })
+                    .unwrap();
+
+                previous_idx = Some(idx);
+                battery_joltage = (battery_joltage * 10) + *digit as u64;
+            }
+
+            trace!("{:?} -> {}", digits, battery_joltage);
+
+            battery_joltage
+        });
+
+        bank_joltages.into_iter().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+987654321111111
+811111111111119
+234234234234278
+818181911112111
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day3::solve_part_1(EXAMPLE).unwrap(), 357);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day3::solve_part_2(EXAMPLE).unwrap(), 3121910778619);
+    }
 }
@@ -1,29 +1,15 @@
-use std::{
-    env,
-    fs::File,
-    io::{BufRead, BufReader},
-    ops::RangeInclusive,
-    process,
-};
-
-fn main() {
-    println!("----- Day 5 -----");
+use std::ops::RangeInclusive;
 
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("No input file specified");
-        process::exit(1);
-    }
-
-    let input_path = &args[1];
-    println!("Input File: {}", input_path);
+use advent_of_code_common::{
+    Input, Solution,
+    bigint::BigUint,
+    error::{ParseError, parse_number},
+};
 
-    println!("Part 1: {}", solve_part_1(input_path));
-    println!("Part 2: {}", solve_part_2(input_path));
-}
+pub struct Day5;
 
 #[derive(Default)]
-struct Database {
+pub struct Database {
     // NOTE: For better efficiency, could index by the start of the range
     //       This would reduce how many entries we need to search through
     id_ranges: Vec<RangeInclusive<u64>>,
@@ -55,56 +41,114 @@ impl Database {
     }
 }
 
-fn read_lines(path: &str) -> impl Iterator<Item = String> {
-    let file = File::open(path).expect("Unable to open input file");
-    let reader = BufReader::new(file);
-
-    reader.lines().filter_map(|l| l.ok())
-}
-
-fn parse_input(input_path: &str) -> (Database, Vec<u64>) {
-    let mut input_iter = read_lines(input_path);
-
-    // Parse ID ranges up until 1st empty line
-    let mut database = Database::default();
-
-    while let Some(id_range_line) = input_iter.next() {
-        if let Some((start_str, end_str)) = id_range_line.trim().split_once("-") {
-            let start = start_str.parse::<u64>().unwrap();
-            let end = end_str.parse::<u64>().unwrap();
+impl Solution for Day5 {
+    type Input = (Database, Vec<u64>);
+    type Answer1 = usize;
+    type Answer2 = BigUint;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut sections = Input::new(input).sections();
+
+        // Parse ID ranges up until 1st empty line
+        let mut database = Database::default();
+        let id_range_lines = sections.next().map(|s| s.lines());
+
+        for (line_number, id_range_line) in id_range_lines.into_iter().flatten() {
+            let id_range_line = id_range_line.trim();
+            let (start_str, end_str) = id_range_line.split_once("-").ok_or_else(|| {
+                ParseError::new(
+                    line_number,
+                    id_range_line,
+                    id_range_line,
+                    "Expected ID range",
+                )
+            })?;
+
+            let start = parse_number::<u64>(line_number, id_range_line, start_str)?;
+            let end = parse_number::<u64>(line_number, id_range_line, end_str)?;
+
+            if start > end {
+                return Err(ParseError::new(
+                    line_number,
+                    id_range_line,
+                    id_range_line,
+                    "ID range starts after it ends",
+                ));
+            }
 
             database.add_id_range(start..=end);
-        } else {
-            break;
         }
+
+        // Parse available IDs from the remaining sections
+        let available_ids = sections
+            .map(|section| section.numbers::<u64>())
+            .collect::<Result<Vec<_>, _>>()?
+            .concat();
+
+        Ok((database, available_ids))
     }
 
-    // Parse available IDs
-    let available_ids = input_iter
-        .filter_map(|l| l.trim().parse::<u64>().ok())
-        .collect();
+    /// Finds how many IDs in the input list are fresh, i.e. are present in the database.
+    fn part1((database, available_ids): &Self::Input) -> Self::Answer1 {
+        available_ids
+            .iter()
+            .filter(|id| database.contains_id(**id))
+            .count()
+    }
 
-    (database, available_ids)
+    /// Finds how many IDs in the database are fresh.
+    fn part2((database, _): &Self::Input) -> Self::Answer2 {
+        // Overlapping ID ranges are merged during parsing, so we can just sum the differences
+        // A range covering every u64 has 2^64 IDs, so the sizes are added as big integers
+        database
+            .id_ranges
+            .iter()
+            .map(|r| BigUint::from(*r.end() - *r.start()) + BigUint::from(1))
+            .sum()
+    }
 }
 
-/// Finds how many IDs in the input list are fresh, i.e. are present in the database.
-fn solve_part_1(input_path: &str) -> usize {
-    let (database, available_ids) = parse_input(input_path);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+3-5
+10-14
+16-20
+12-18
+
+1
+5
+8
+11
+17
+32
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day5::solve_part_1(EXAMPLE).unwrap(), 3);
+    }
 
-    available_ids
-        .iter()
-        .filter(|id| database.contains_id(**id))
-        .count()
-}
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day5::solve_part_2(EXAMPLE).unwrap(), 14);
+    }
 
-/// Finds how many IDs in the database are fresh.
-fn solve_part_2(input_path: &str) -> u64 {
-    let (database, _) = parse_input(input_path);
+    #[test]
+    fn part2_merges_nested_ranges() {
+        // The case from the README that originally produced answers that were too large
+        assert_eq!(Day5::solve_part_2("100-110\n103-107\n\n").unwrap(), 11);
+        assert_eq!(Day5::solve_part_2("103-107\n100-110\n\n").unwrap(), 11);
+    }
 
-    // Overlapping ID ranges are merged during parsing, so we can just sum the differences
-    database
-        .id_ranges
-        .iter()
-        .map(|r| *r.end() - *r.start() + 1)
-        .sum()
+    #[test]
+    fn part2_counts_past_u64() {
+        let input = "0-18446744073709551615\n5-10\n\n";
+        assert_eq!(
+            Day5::solve_part_2(input).unwrap().to_string(),
+            "18446744073709551616"
+        );
+    }
 }
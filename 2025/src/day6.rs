@@ -1,74 +1,9 @@
-use std::{
-    env,
-    fs::File,
-    io::{BufRead, BufReader},
-    process,
-};
-
-fn main() {
-    println!("----- Day 6 -----");
-
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("No input file specified");
-        process::exit(1);
-    }
-
-    let input_path = &args[1];
-    println!("Input File: {}", input_path);
-
-    println!("Part 1: {}", solve_part_1(input_path));
-    println!("Part 2: {}", solve_part_2(input_path));
-}
-
-fn read_lines(path: &str) -> impl Iterator<Item = String> {
-    let file = File::open(path).expect("Unable to open input file");
-    let reader = BufReader::new(file);
-
-    reader.lines().filter_map(|l| l.ok())
-}
+use advent_of_code_common::{Solution, bigint::BigUint, error::ParseError};
 
-/// Calculates the grand total for all problems in the worksheet.
-fn solve_part_1(input_path: &str) -> u64 {
-    let mut input_lines = read_lines(input_path);
-
-    // Parse numbers that need to be added/multiplied
-    let mut number_lines: Vec<Vec<u64>> = Vec::new();
-    let mut grand_total = 0;
-
-    while let Some(line) = input_lines.next() {
-        let values = line.trim().split_whitespace().collect::<Vec<_>>();
-
-        if let Ok(_) = values.first().unwrap().parse::<u64>() {
-            // First value in the line is a number, so add to list of number lines
-            let number_line = values
-                .into_iter()
-                .filter_map(|v| v.parse::<u64>().ok())
-                .collect::<Vec<_>>();
-
-            number_lines.push(number_line);
-        } else {
-            // First value is an operator, so start calculating grand total
-            for (problem_idx, &op) in values.iter().enumerate() {
-                let problem_nums = number_lines.iter().map(|l| l[problem_idx]);
-                let intermediate_total = match op {
-                    "+" => problem_nums.sum(),
-                    "*" => problem_nums.fold(1, |acc, x| acc * x),
-                    _ => panic!("Unexpected operator '{}'", op),
-                };
-
-                grand_total += intermediate_total;
-            }
-
-            break;
-        }
-    }
-
-    grand_total
-}
+pub struct Day6;
 
 #[derive(Clone, Copy, Debug)]
-enum Operator {
+pub enum Operator {
     Add,
     Multiply,
 }
@@ -88,97 +23,302 @@ impl TryFrom<char> for Operator {
 }
 
 impl Operator {
-    pub fn calculate(&self, numbers: &[u64]) -> u64 {
+    pub fn calculate(&self, numbers: &[BigUint]) -> BigUint {
         match self {
             Operator::Add => numbers.iter().sum(),
-            Operator::Multiply => numbers.iter().cloned().reduce(|acc, x| acc * x).unwrap(),
+            Operator::Multiply => numbers.iter().product(),
         }
     }
 }
 
-/// Calculates the grand total for all problems in the worksheet using different place value interpretations.
-fn solve_part_2(input_path: &str) -> u64 {
-    let mut input_lines = read_lines(input_path);
-
-    // Parse individual characters
-    let mut digit_lines: Vec<Vec<Option<u8>>> = Vec::new();
-    let mut operators: Vec<Operator> = Vec::with_capacity(0);
-
-    while let Some(line) = input_lines.next() {
-        // Determine what type of line to parse based on 1st non-empty character
-        let first_char = line
-            .chars()
-            .skip_while(|c| c.is_whitespace())
-            .next()
-            .unwrap();
-
-        if first_char.is_ascii_digit() {
-            // Parse as a digit line
-            let digit_line = line
-                .trim_end()
-                .chars()
-                .map(|c| c.to_digit(10).map(|d| d as u8))
-                .collect::<Vec<_>>();
+/// Worksheet where each character in the number rows has been parsed as an optional digit.
+#[derive(Clone, Debug)]
+pub struct Worksheet {
+    digit_lines: Vec<Vec<Option<u8>>>,
+    operators: Vec<Operator>,
+}
 
-            digit_lines.push(digit_line);
-        } else {
-            // Parse as an operator line
-            operators = line
-                .trim_end()
-                .chars()
-                .filter_map(|c| Operator::try_from(c).ok())
+impl Solution for Day6 {
+    type Input = Worksheet;
+    type Answer1 = BigUint;
+    type Answer2 = BigUint;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        // Parse individual characters
+        let mut digit_lines: Vec<Vec<Option<u8>>> = Vec::new();
+        let mut number_rows: Vec<(usize, &str)> = Vec::new();
+        let mut operator_row: Option<(usize, &str, Vec<&str>, Vec<Operator>)> = None;
+        let mut line_count = 0;
+
+        for (idx, line) in input.lines().enumerate() {
+            line_count = idx + 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            // Determine what type of line to parse based on 1st non-empty character
+            let first_char = line.chars().find(|c| !c.is_whitespace()).unwrap();
+            let line = line.trim_end();
+
+            if first_char.is_ascii_digit() {
+                // Parse as a digit line
+                let digit_line = line
+                    .char_indices()
+                    .map(|(char_idx, c)| match c {
+                        ' ' => Ok(None),
+                        _ => c.to_digit(10).map(|d| Some(d as u8)).ok_or_else(|| {
+                            let token = &line[char_idx..(char_idx + c.len_utf8())];
+                            ParseError::new(idx + 1, line, token, "Expected digit")
+                        }),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                digit_lines.push(digit_line);
+                number_rows.push((idx + 1, line));
+            } else {
+                // Parse as an operator line
+                let operator_tokens = line
+                    .char_indices()
+                    .filter(|(_, c)| *c != ' ')
+                    .map(|(char_idx, c)| &line[char_idx..(char_idx + c.len_utf8())])
+                    .collect::<Vec<_>>();
+
+                let operator_line = operator_tokens
+                    .iter()
+                    .map(|token| {
+                        Operator::try_from(token.chars().next().unwrap()).map_err(|_| {
+                            ParseError::new(idx + 1, line, token, "Unexpected operator")
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if digit_lines.is_empty() {
+                    return Err(ParseError::new(
+                        idx + 1,
+                        line,
+                        line,
+                        "Expected number rows before the operators",
+                    ));
+                }
+
+                operator_row = Some((idx + 1, line, operator_tokens, operator_line));
+                break;
+            }
+        }
+
+        let Some((operator_line_number, operator_line, operator_tokens, operators)) = operator_row
+        else {
+            return Err(ParseError::end_of_input(
+                line_count + 1,
+                "Expected a line of operators",
+            ));
+        };
+
+        // Both parts expect every row to have a number for each operator
+        for &(line_number, line) in &number_rows {
+            let numbers = line
+                .split(' ')
+                .filter(|n| !n.is_empty())
                 .collect::<Vec<_>>();
 
-            break;
+            if let Some(extra) = numbers.get(operators.len()) {
+                return Err(ParseError::new(
+                    line_number,
+                    line,
+                    extra,
+                    "Expected an operator for this number",
+                ));
+            }
+
+            if numbers.len() < operators.len() {
+                return Err(ParseError::new(
+                    operator_line_number,
+                    operator_line,
+                    operator_tokens[numbers.len()],
+                    format!(
+                        "Expected a number for this operator on line {}",
+                        line_number
+                    ),
+                ));
+            }
+        }
+
+        // .trim_end() was used to remove the newline, but this also removes normal spaces
+        // So add blanks to the end of any digit list that's too short
+        let expected_line_length = digit_lines.iter().map(|l| l.len()).max().unwrap();
+
+        for digit_line in digit_lines
+            .iter_mut()
+            .filter(|l| l.len() < expected_line_length)
+        {
+            digit_line.resize(expected_line_length, Option::None);
         }
+
+        // Part 2 also expects each problem's numbers to be separated by a blank column
+        let column_has_digits = |col: usize| digit_lines.iter().any(|l| l[col].is_some());
+        let problem_count = (0..expected_line_length)
+            .filter(|&col| column_has_digits(col) && (col == 0 || !column_has_digits(col - 1)))
+            .count();
+
+        if problem_count != operators.len() {
+            let token = operator_tokens
+                .get(problem_count)
+                .copied()
+                .unwrap_or(&operator_line[operator_line.len()..]);
+
+            return Err(ParseError::new(
+                operator_line_number,
+                operator_line,
+                token,
+                format!(
+                    "Expected one operator per column of numbers, but found {} columns",
+                    problem_count
+                ),
+            ));
+        }
+
+        Ok(Worksheet {
+            digit_lines,
+            operators,
+        })
     }
 
-    // .trim_end() was used to remove the newline, but this also removes normal spaces
-    // So add blanks to the end of any digit list that's too short
-    let expected_line_length = digit_lines.iter().map(|l| l.len()).max().unwrap();
+    /// Calculates the grand total for all problems in the worksheet.
+    fn part1(worksheet: &Self::Input) -> Self::Answer1 {
+        // Numbers in each row are separated by blanks
+        let number_lines = worksheet
+            .digit_lines
+            .iter()
+            .map(|digit_line| {
+                digit_line
+                    .split(|d| d.is_none())
+                    .filter(|digits| !digits.is_empty())
+                    .map(|digits| BigUint::from_digits(digits.iter().flatten().copied()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut grand_total = BigUint::zero();
+
+        for (problem_idx, operator) in worksheet.operators.iter().enumerate() {
+            let problem_nums = number_lines
+                .iter()
+                .map(|l| l[problem_idx].clone())
+                .collect::<Vec<_>>();
+
+            grand_total += operator.calculate(&problem_nums);
+        }
 
-    for digit_line in digit_lines
-        .iter_mut()
-        .filter(|l| l.len() < expected_line_length)
-    {
-        digit_line.resize(expected_line_length, Option::None);
+        grand_total
     }
 
-    // Loop through each digit location and either combine them or calculate the problem answer
-    // Idea is that when all digits are missing, we're in-between two problems
-    let mut grand_total = 0;
-    let mut problem_idx = 0;
-    let mut problem_numbers: Vec<u64> = Vec::new();
+    /// Calculates the grand total for all problems in the worksheet using different place value interpretations.
+    fn part2(worksheet: &Self::Input) -> Self::Answer2 {
+        let Worksheet {
+            digit_lines,
+            operators,
+        } = worksheet;
+
+        let expected_line_length = digit_lines.first().map(|l| l.len()).unwrap_or(0);
+
+        // Loop through each digit location and either combine them or calculate the problem answer
+        // Idea is that when all digits are missing, we're in-between two problems (a run of blank
+        // columns is a single gap, and blanks before the first problem don't end anything)
+        let mut grand_total = BigUint::zero();
+        let mut problem_idx = 0;
+        let mut problem_numbers: Vec<BigUint> = Vec::new();
+
+        for digit_idx in 0..expected_line_length {
+            // Attempt to combine the digits at this index
+            let mut digits = digit_lines.iter().filter_map(|l| l[digit_idx]).peekable();
+
+            if digits.peek().is_some() {
+                problem_numbers.push(BigUint::from_digits(digits))
+            } else if !problem_numbers.is_empty() {
+                // No digits in this location, so calculate answer for this problem
+                let operator = operators[problem_idx];
+                let problem_answer = operator.calculate(&problem_numbers);
+
+                grand_total += problem_answer;
+                problem_idx += 1;
+                problem_numbers.clear();
+            }
+        }
 
-    for digit_idx in 0..expected_line_length {
-        // Attempt to combine the digits at this index
-        let problem_number = digit_lines
-            .iter()
-            .map(|l| l[digit_idx])
-            .filter_map(|d| d)
-            .map(|d| d as u64)
-            .reduce(|acc, d| (acc * 10) + d);
-
-        if let Some(number) = problem_number {
-            problem_numbers.push(number)
-        } else {
-            // No digits in this location, so calculate answer for this problem
+        // Handle last calculation if required
+        if problem_idx < operators.len() && !problem_numbers.is_empty() {
             let operator = operators[problem_idx];
             let problem_answer = operator.calculate(&problem_numbers);
 
             grand_total += problem_answer;
-            problem_idx += 1;
-            problem_numbers.clear();
         }
+
+        grand_total
     }
+}
 
-    // Handle last calculation if required
-    if problem_idx < operators.len() && !problem_numbers.is_empty() {
-        let operator = operators[problem_idx];
-        let problem_answer = operator.calculate(&problem_numbers);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Trailing spaces are significant here, so each line is written out explicitly
+    const EXAMPLE: &str = concat!(
+        "123 328  51 64 \n",
+        " 45 64  387 23 \n",
+        "  6 98  215 314\n",
+        "*   +   *   +  \n",
+    );
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day6::solve_part_1(EXAMPLE).unwrap(), 4277556);
+    }
 
-        grand_total += problem_answer;
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day6::solve_part_2(EXAMPLE).unwrap(), 3263827);
     }
 
-    grand_total
+    #[test]
+    fn rows_need_a_number_per_operator() {
+        let error = Day6::parse("1 2\n+ + +\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+
+        let error = Day6::parse("1 2 3\n4 5\n+ + +\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 5));
+
+        let error = Day6::parse("1 2 3\n+ +\n").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.token.as_str()),
+            (1, 5, "3")
+        );
+    }
+
+    #[test]
+    fn problems_need_separate_columns() {
+        // Each row has two numbers, but no column is blank in every row
+        let error = Day6::parse("12 3\n1 23\n+ *\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 3));
+    }
+
+    #[test]
+    fn part2_blank_column_runs_are_one_gap() {
+        assert_eq!(Day6::solve_part_2("1   2\n+   *\n").unwrap(), 3);
+        assert_eq!(Day6::solve_part_2(" 1 2\n + *\n").unwrap(), 3);
+        assert_eq!(Day6::solve_part_2(" 1   2  \n + * \n").unwrap(), 3);
+    }
+
+    #[test]
+    fn products_past_u64() {
+        let input = "9999999999\n9999999999\n*         \n";
+
+        assert_eq!(
+            Day6::solve_part_1(input).unwrap().to_string(),
+            "99999999980000000001"
+        );
+        assert_eq!(
+            Day6::solve_part_2(input).unwrap().to_string(),
+            "90438207500880449001"
+        );
+    }
 }
@@ -1,45 +1,13 @@
-use std::{
-    cmp::Reverse,
-    collections::HashSet,
-    env,
-    fs::File,
-    io::{BufRead, BufReader},
-    process,
-};
-
-fn main() {
-    println!("----- Day 8 -----");
-
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("No input file specified");
-        process::exit(1);
-    }
-
-    let input_path = &args[1];
-    println!("Input File: {}", input_path);
+use std::{cmp::Reverse, collections::HashSet};
 
-    println!("Part 1: {}", solve_part_1(input_path));
-    println!("Part 2: {}", solve_part_2(input_path));
-}
+use advent_of_code_common::{Solution, error::ParseError, geometry::Point3};
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-struct Coordinate {
-    x: u32,
-    y: u32,
-    z: u32,
-}
+pub struct Day8;
 
-impl Coordinate {
-    // Calculating square root is slow and not necessary for direct comparisons, so I left it in squared form
-    pub const fn euclidean_distance_squared(&self, other: &Coordinate) -> u64 {
-        let x_diff = self.x.abs_diff(other.x) as u64;
-        let y_diff = self.y.abs_diff(other.y) as u64;
-        let z_diff = self.z.abs_diff(other.z) as u64;
+/// Number of closest pairs that part 1 connects. The example uses 10 instead.
+const PAIR_COUNT: usize = 1000;
 
-        (x_diff * x_diff) + (y_diff * y_diff) + (z_diff * z_diff)
-    }
-}
+pub type Coordinate = Point3<u32>;
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 struct Edge {
@@ -97,34 +65,37 @@ impl Circuit {
     }
 }
 
-fn read_lines(path: &str) -> impl Iterator<Item = String> {
-    let file = File::open(path).expect("Unable to open input file");
-    let reader = BufReader::new(file);
+fn parse_input<'a>(
+    input_lines: impl Iterator<Item = &'a str>,
+) -> Result<Vec<Coordinate>, ParseError> {
+    let mut junction_boxes = Vec::new();
+    let mut line_count = 0;
+
+    for (idx, line) in input_lines.enumerate() {
+        line_count = idx + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
 
-    reader.lines().filter_map(|l| l.ok())
-}
+        junction_boxes.push(Coordinate::parse(idx + 1, line)?);
+    }
 
-fn parse_input(input_lines: &mut impl Iterator<Item = String>) -> Vec<Coordinate> {
-    input_lines
-        .map(|l| {
-            let mut coordinates = l.trim().split(",");
-            let x = coordinates.next().unwrap().parse::<u32>().unwrap();
-            let y = coordinates.next().unwrap().parse::<u32>().unwrap();
-            let z = coordinates.next().unwrap().parse::<u32>().unwrap();
-
-            Coordinate { x, y, z }
-        })
-        .collect()
+    if junction_boxes.is_empty() {
+        return Err(ParseError::end_of_input(
+            line_count + 1,
+            "Expected at least one junction box",
+        ));
+    }
+
+    Ok(junction_boxes)
 }
 
 fn generate_all_edges(junction_box_coords: &[Coordinate]) -> Vec<Edge> {
     let mut edges = Vec::new();
 
-    for i in 0..junction_box_coords.len() {
-        let coord1 = junction_box_coords[i];
-
-        for j in (i + 1)..junction_box_coords.len() {
-            let coord2 = junction_box_coords[j];
+    for (i, &coord1) in junction_box_coords.iter().enumerate() {
+        for &coord2 in &junction_box_coords[(i + 1)..] {
             edges.push(Edge::new(coord1, coord2));
         }
     }
@@ -147,15 +118,12 @@ fn add_edge(circuits: &mut Vec<Circuit>, edge: Edge) {
     circuits.push(merged_circuit);
 }
 
-/// Finds the three largest circuits when connecting junction boxes with the shortest edges only,
-/// then calculates the product of the circuit sizes.
-fn solve_part_1(input_path: &str) -> usize {
-    let mut input_lines = read_lines(input_path);
-    let junction_box_coords = parse_input(&mut input_lines);
-
+/// Connects the given number of closest junction box pairs, then calculates the product of the
+/// three largest circuit sizes.
+pub fn largest_circuits_product(junction_box_coords: &[Coordinate], pair_count: usize) -> usize {
     // Calculate all unique edges and their euclidean distances
     // Then sort by increasing distance
-    let mut edges = generate_all_edges(&junction_box_coords);
+    let mut edges = generate_all_edges(junction_box_coords);
     edges.sort_by_key(|f| f.euclid_dist_sq);
 
     // Setup initial circuits where only a single junction box is present
@@ -165,12 +133,7 @@ fn solve_part_1(input_path: &str) -> usize {
         .collect();
 
     // Then add all edges for required number of pairs
-    // Strangely, the example wants '10' (count / 2), but main input wants '1000' (count)
-    if input_path.contains("example") {
-        edges.truncate(10);
-    } else {
-        edges.truncate(1000);
-    }
+    edges.truncate(pair_count);
 
     for edge in edges {
         add_edge(&mut circuits, edge);
@@ -186,37 +149,94 @@ fn solve_part_1(input_path: &str) -> usize {
     circuit_sizes.sort_by_key(|s| Reverse(*s));
     circuit_sizes.truncate(TOP_CIRCUIT_COUNT);
 
-    circuit_sizes
-        .iter()
-        .cloned()
-        .reduce(|acc, s| acc * s)
-        .unwrap()
+    circuit_sizes.iter().product()
 }
 
-/// Continuously adds edges until all circuits have at least 1 edge, then multiplies the X
-/// coordinates of the junction boxes in the final edge that was added.
-fn solve_part_2(input_path: &str) -> u64 {
-    let mut input_lines = read_lines(input_path);
-    let junction_box_coords = parse_input(&mut input_lines);
+impl Solution for Day8 {
+    type Input = Vec<Coordinate>;
+    type Answer1 = usize;
+    type Answer2 = u64;
 
-    // Calculate all unique edges and their euclidean distances
-    // Then sort by increasing distance
-    let mut edges = generate_all_edges(&junction_box_coords);
-    edges.sort_by_key(|f| f.euclid_dist_sq);
-
-    // Setup initial circuits where only a single junction box is present
-    let mut circuits: Vec<Circuit> = junction_box_coords
-        .iter()
-        .map(|c| Circuit::from_junction_box(*c))
-        .collect();
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input.lines())
+    }
 
-    for edge in edges {
-        add_edge(&mut circuits, edge);
+    /// Finds the three largest circuits when connecting junction boxes with the shortest edges only,
+    /// then calculates the product of the circuit sizes.
+    fn part1(junction_box_coords: &Self::Input) -> Self::Answer1 {
+        largest_circuits_product(junction_box_coords, PAIR_COUNT)
+    }
 
-        if circuits.iter().all(|c| !c.edges.is_empty()) {
-            return edge.start.x as u64 * edge.end.x as u64;
+    /// Continuously adds edges until all circuits have at least 1 edge, then multiplies the X
+    /// coordinates of the junction boxes in the final edge that was added.
+    fn part2(junction_box_coords: &Self::Input) -> Self::Answer2 {
+        // Calculate all unique edges and their euclidean distances
+        // Then sort by increasing distance
+        let mut edges = generate_all_edges(junction_box_coords);
+        edges.sort_by_key(|f| f.euclid_dist_sq);
+
+        // Setup initial circuits where only a single junction box is present
+        let mut circuits: Vec<Circuit> = junction_box_coords
+            .iter()
+            .map(|c| Circuit::from_junction_box(*c))
+            .collect();
+
+        for edge in edges {
+            add_edge(&mut circuits, edge);
+
+            if circuits.iter().all(|c| !c.edges.is_empty()) {
+                return edge.start.x as u64 * edge.end.x as u64;
+            }
         }
+
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689
+";
+
+    #[test]
+    fn part1_example() {
+        // Strangely, the example wants '10' (count / 2), but main input wants '1000' (count)
+        let junction_box_coords = Day8::parse(EXAMPLE).unwrap();
+        assert_eq!(largest_circuits_product(&junction_box_coords, 10), 40);
     }
 
-    0
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day8::solve_part_2(EXAMPLE).unwrap(), 25272);
+    }
+
+    #[test]
+    fn empty_input_is_an_error() {
+        for input in ["", "\n\n"] {
+            let error = Day8::parse(input).unwrap_err();
+            assert_eq!(error.message, "Expected at least one junction box");
+        }
+    }
 }
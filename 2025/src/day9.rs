@@ -1,30 +1,11 @@
-use std::{
-    env,
-    fs::File,
-    io::{BufRead, BufReader},
-    process,
-};
-
 use geo::{Coord, LineString, Polygon, PreparedGeometry, Rect, Relate};
 
-fn main() {
-    println!("----- Day 9 -----");
-
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("No input file specified");
-        process::exit(1);
-    }
-
-    let input_path = &args[1];
-    println!("Input File: {}", input_path);
+use advent_of_code_common::{Solution, error::ParseError, geometry::parse_coordinates};
 
-    println!("Part 1: {}", solve_part_1(input_path));
-    println!("Part 2: {}", solve_part_2(input_path));
-}
+pub struct Day9;
 
 #[derive(Clone, Copy, Debug)]
-struct Coordinate {
+pub struct Coordinate {
     x: u64,
     y: u64,
 }
@@ -56,80 +37,99 @@ impl Coordinate {
     }
 }
 
-fn read_lines(path: &str) -> impl Iterator<Item = String> {
-    let file = File::open(path).expect("Unable to open input file");
-    let reader = BufReader::new(file);
-
-    reader
-        .lines()
-        .filter_map(|l| l.ok())
-        .filter(|l| !l.is_empty())
-}
-
-fn parse_input(input_lines: &mut impl Iterator<Item = String>) -> Vec<Coordinate> {
-    input_lines
-        .map(|l| {
-            let mut coordinates = l.trim().split(",");
-            let x = coordinates.next().unwrap().parse::<u64>().unwrap();
-            let y = coordinates.next().unwrap().parse::<u64>().unwrap();
-
-            Coordinate { x, y }
-        })
-        .collect()
-}
-
-/// Finds the largest rectangular area that can be made using two of the red tiles in the grid.
-fn solve_part_1(input_path: &str) -> u64 {
-    let mut input_lines = read_lines(input_path);
-    let coordinates = parse_input(&mut input_lines);
-
-    let mut largest_rectangle_area = 0;
+fn parse_input<'a>(
+    input_lines: impl Iterator<Item = &'a str>,
+) -> Result<Vec<Coordinate>, ParseError> {
+    let mut coordinates_list = Vec::new();
 
-    for i in 0..coordinates.len() {
-        let coord1 = coordinates[i];
-
-        for j in (i + 1)..coordinates.len() {
-            let coord2 = coordinates[j];
-            let rectangle_area = coord1.rectangle_area(&coord2);
-
-            largest_rectangle_area = largest_rectangle_area.max(rectangle_area);
+    for (idx, line) in input_lines.enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
         }
+
+        let [x, y] = parse_coordinates(idx + 1, line)?;
+        coordinates_list.push(Coordinate { x, y });
     }
 
-    largest_rectangle_area
+    Ok(coordinates_list)
 }
 
-/// Finds the largest rectangular area where:
-/// - The 2 selected corners are red tiles
-/// - Only red + green tiles are swapped
-fn solve_part_2(input_path: &str) -> u64 {
-    let mut input_lines = read_lines(input_path);
-    let coordinates = parse_input(&mut input_lines);
+impl Solution for Day9 {
+    type Input = Vec<Coordinate>;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-    // Unfortunately, I couldn't figure out a way to do this without libraries...
-    //
-    // I had an idea about iterating over 4 adjacent coordinates at a time and looking for "U"
-    // shapes, but wasn't able to get it working on the example input
-    let polygon = Polygon::new(LineString::from_iter(coordinates.iter()), Vec::new());
-    let prepared_polygon = PreparedGeometry::from(polygon);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input.lines())
+    }
 
-    let mut largest_rectangle_area = 0;
+    /// Finds the largest rectangular area that can be made using two of the red tiles in the grid.
+    fn part1(coordinates: &Self::Input) -> Self::Answer1 {
+        let mut largest_rectangle_area = 0;
 
-    for i in 0..coordinates.len() {
-        let coord1 = coordinates[i];
+        for (i, coord1) in coordinates.iter().enumerate() {
+            for coord2 in &coordinates[(i + 1)..] {
+                let rectangle_area = coord1.rectangle_area(coord2);
 
-        for j in (i + 1)..coordinates.len() {
-            let coord2 = coordinates[j];
+                largest_rectangle_area = largest_rectangle_area.max(rectangle_area);
+            }
+        }
 
-            let rectangle = Rect::new(coord1, coord2);
-            let intersection_matrix = prepared_polygon.relate(&rectangle);
+        largest_rectangle_area
+    }
 
-            if intersection_matrix.is_covers() {
-                let area = coord1.rectangle_area(&coord2);
-                largest_rectangle_area = largest_rectangle_area.max(area);
+    /// Finds the largest rectangular area where:
+    /// - The 2 selected corners are red tiles
+    /// - Only red + green tiles are swapped
+    fn part2(coordinates: &Self::Input) -> Self::Answer2 {
+        // Unfortunately, I couldn't figure out a way to do this without libraries...
+        //
+        // I had an idea about iterating over 4 adjacent coordinates at a time and looking for "U"
+        // shapes, but wasn't able to get it working on the example input
+        let polygon = Polygon::new(LineString::from_iter(coordinates.iter()), Vec::new());
+        let prepared_polygon = PreparedGeometry::from(polygon);
+
+        let mut largest_rectangle_area = 0;
+
+        for (i, coord1) in coordinates.iter().enumerate() {
+            for coord2 in &coordinates[(i + 1)..] {
+                let rectangle = Rect::new(coord1, coord2);
+                let intersection_matrix = prepared_polygon.relate(&rectangle);
+
+                if intersection_matrix.is_covers() {
+                    let area = coord1.rectangle_area(coord2);
+                    largest_rectangle_area = largest_rectangle_area.max(area);
+                }
             }
         }
+
+        largest_rectangle_area
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day9::solve_part_1(EXAMPLE).unwrap(), 50);
     }
 
-    largest_rectangle_area
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day9::solve_part_2(EXAMPLE).unwrap(), 24);
+    }
 }
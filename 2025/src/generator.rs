@@ -0,0 +1,460 @@
+//! Random input generators for each day, used by `aoc gen` and for testing solvers on more than
+//! the real input.
+//!
+//! Every generator produces text that the day's parser accepts, shaped like the real input (e.g.
+//! disjoint ID ranges, or a manifold whose splitters line up with the beams), but with `size`
+//! controlling how many records there are.
+
+use std::{collections::HashSet, fmt::Write};
+
+use advent_of_code_common::generator::Rng;
+
+use crate::{day3::BATTERY_COUNT, day11::*};
+
+/// Dial rotations, e.g. `L68`, mostly within one revolution.
+pub fn day1(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let direction = if rng.chance(0.5) { 'L' } else { 'R' };
+        let distance = if rng.chance(0.1) {
+            // Whole revolutions leave the dial where it started, which is easy to miscount
+            100 * rng.range(0..=9)
+        } else if rng.chance(0.2) {
+            rng.range(0..=999)
+        } else {
+            rng.range(1..=99)
+        };
+
+        writeln!(input, "{}{}", direction, distance).unwrap();
+    }
+
+    input
+}
+
+/// Disjoint ID ranges on a single line, e.g. `11-22,95-115`, spanning a variety of digit counts.
+pub fn day2(rng: &mut Rng, size: usize) -> String {
+    let mut id_ranges: Vec<(u64, u64)> = (0..size)
+        .map(|_| {
+            let digits = rng.range(1..=10) as u32;
+            let start = rng.range(10_u64.pow(digits - 1)..=10_u64.pow(digits) - 1);
+            let span_digits = rng.range(0..=3) as u32;
+            let span = rng.below(10_u64.pow(span_digits));
+
+            (start, start + span)
+        })
+        .collect();
+
+    // Real ranges never overlap
+    id_ranges.sort_unstable();
+    let mut last_end = None;
+    id_ranges.retain(|&(start, end)| {
+        let is_disjoint = last_end.is_none_or(|last_end| start > last_end);
+        if is_disjoint {
+            last_end = Some(end);
+        }
+
+        is_disjoint
+    });
+    rng.shuffle(&mut id_ranges);
+
+    let id_ranges: Vec<String> = id_ranges
+        .iter()
+        .map(|(start, end)| format!("{}-{}", start, end))
+        .collect();
+
+    format!("{}\n", id_ranges.join(","))
+}
+
+/// Battery banks of joltage digits, e.g. `987654321111111`.
+pub fn day3(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let bank_len = rng.range(BATTERY_COUNT as u64..=3 * BATTERY_COUNT as u64);
+        for _ in 0..bank_len {
+            input.push(char::from(b'0' + rng.range(1..=9) as u8));
+        }
+
+        input.push('\n');
+    }
+
+    input
+}
+
+/// Square grid of paper rolls (`@`) and empty space (`.`).
+pub fn day4(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let density = 0.4 + rng.below(40) as f64 / 100.0;
+    let mut input = String::new();
+
+    for _ in 0..size {
+        for _ in 0..size {
+            input.push(if rng.chance(density) { '@' } else { '.' });
+        }
+
+        input.push('\n');
+    }
+
+    input
+}
+
+/// Fresh ID ranges (which often overlap or touch), then a list of available IDs.
+pub fn day5(rng: &mut Rng, size: usize) -> String {
+    let scale = 10 * size.max(1) as u64;
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let start = rng.range(1..=scale);
+        let end = start + rng.below(scale / 4 + 1);
+        writeln!(input, "{}-{}", start, end).unwrap();
+    }
+
+    input.push('\n');
+
+    for _ in 0..size {
+        writeln!(input, "{}", rng.range(1..=scale + scale / 4)).unwrap();
+    }
+
+    input
+}
+
+/// Worksheet of problems written in columns, where each problem's numbers are aligned to the
+/// left or right, followed by a line of operators.
+pub fn day6(rng: &mut Rng, size: usize) -> String {
+    let row_count = rng.range(2..=4) as usize;
+    let mut rows = vec![String::new(); row_count];
+    let mut operators = String::new();
+
+    for problem_idx in 0..size.max(1) {
+        if problem_idx > 0 {
+            // Problems are separated by a column of spaces
+            rows.iter_mut().for_each(|row| row.push(' '));
+            operators.push(' ');
+        }
+
+        let width = rng.range(1..=4) as usize;
+        let align_left = rng.chance(0.5);
+
+        // At least one number uses the full width, so no column in the problem is blank
+        let full_width_row = rng.index(row_count);
+        for (row_idx, row) in rows.iter_mut().enumerate() {
+            let digit_count = if row_idx == full_width_row {
+                width
+            } else {
+                rng.range(1..=width as u64) as usize
+            };
+
+            let number: String = (0..digit_count)
+                .map(|_| char::from(b'0' + rng.range(1..=9) as u8))
+                .collect();
+
+            if align_left {
+                write!(row, "{:<width$}", number).unwrap();
+            } else {
+                write!(row, "{:>width$}", number).unwrap();
+            }
+        }
+
+        let operator = if rng.chance(0.5) { '+' } else { '*' };
+        write!(operators, "{:<width$}", operator).unwrap();
+    }
+
+    let mut input = rows.join("\n");
+    writeln!(input, "\n{}", operators).unwrap();
+    input
+}
+
+/// Manifold with a start at the top and splitters on every other row, placed where beams can
+/// reach them.
+pub fn day7(rng: &mut Rng, size: usize) -> String {
+    let splitter_rows = size.max(1);
+    let cols = 2 * splitter_rows + 1;
+    let middle = splitter_rows;
+    let density = 0.5 + rng.below(50) as f64 / 100.0;
+
+    let mut input = String::new();
+    let mut row = vec!['.'; cols];
+    row[middle] = 'S';
+    input.extend(row.iter());
+    input.push('\n');
+
+    for splitter_row in 1..=splitter_rows {
+        // Blank row between each row of splitters
+        input.extend(std::iter::repeat_n('.', cols));
+        input.push('\n');
+
+        // Beams can only reach every other column within this distance of the start
+        let reach = splitter_row - 1;
+        let mut row = vec!['.'; cols];
+        for col in ((middle - reach)..=(middle + reach)).step_by(2) {
+            if rng.chance(density) {
+                row[col] = '^';
+            }
+        }
+
+        input.extend(row.iter());
+        input.push('\n');
+    }
+
+    input.extend(std::iter::repeat_n('.', cols));
+    input.push('\n');
+    input
+}
+
+/// Junction box positions in 3D space, e.g. `162,817,812`.
+pub fn day8(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    // Connecting boxes needs at least two of them
+    for _ in 0..size.max(2) {
+        let [x, y, z] = [(); 3].map(|_| rng.range(0..=99_999));
+        writeln!(input, "{},{},{}", x, y, z).unwrap();
+    }
+
+    input
+}
+
+/// Red tiles at the corners of a rectilinear polygon, in order around its edge.
+///
+/// The polygon is built from `size` columns, each spanning a vertical interval that overlaps its
+/// neighbour's, so the outline never touches or crosses itself.
+pub fn day9(rng: &mut Rng, size: usize) -> String {
+    let column_count = size.max(1);
+    let level_count = 2 * column_count as u64 + 4;
+
+    // (bottom, top) levels of each column
+    let mut columns: Vec<(u64, u64)> = Vec::with_capacity(column_count);
+    while columns.len() < column_count {
+        // Columns are at least two levels tall, otherwise a column at the very bottom or top
+        // could leave no room for its neighbour
+        let bottom = rng.range(0..=level_count - 3);
+        let top = rng.range(bottom + 2..=level_count - 1);
+
+        // Neighbouring columns must overlap, without sharing an edge (which would add extra
+        // corners in the middle of a straight line)
+        let fits = columns.last().is_none_or(|&(prev_bottom, prev_top)| {
+            bottom.max(prev_bottom) < top.min(prev_top) && bottom != prev_bottom && top != prev_top
+        });
+
+        if fits {
+            columns.push((bottom, top));
+        }
+    }
+
+    // Spread the columns and levels out to random tile positions
+    let mut spread = |count: usize| -> Vec<u64> {
+        (0..count)
+            .scan(0, |position, _| {
+                *position += rng.range(1..=1000);
+                Some(*position)
+            })
+            .collect()
+    };
+    let xs = spread(column_count + 1);
+    let ys = spread(level_count as usize);
+
+    // Clockwise around the outline: left to right along the tops, then back along the bottoms
+    let mut corners: Vec<(u64, u64)> = Vec::with_capacity(4 * column_count);
+    for (idx, &(_, top)) in columns.iter().enumerate() {
+        corners.push((xs[idx], ys[top as usize]));
+        corners.push((xs[idx + 1], ys[top as usize]));
+    }
+
+    for (idx, &(bottom, _)) in columns.iter().enumerate().rev() {
+        corners.push((xs[idx + 1], ys[bottom as usize]));
+        corners.push((xs[idx], ys[bottom as usize]));
+    }
+
+    let mut input = String::new();
+    for (x, y) in corners {
+        writeln!(input, "{},{}", x, y).unwrap();
+    }
+
+    input
+}
+
+/// Machines whose target lights and joltages are produced by pressing random buttons, so they're
+/// always solvable.
+pub fn day10(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let light_count = rng.range(2..=8) as usize;
+        let button_count = rng.range(1..=light_count as u64 + 2) as usize;
+
+        let buttons: Vec<Vec<usize>> = (0..button_count)
+            .map(|_| {
+                let mut indexes: Vec<usize> = (0..light_count).collect();
+                rng.shuffle(&mut indexes);
+                indexes.truncate(rng.range(1..=light_count as u64) as usize);
+                indexes.sort_unstable();
+                indexes
+            })
+            .collect();
+
+        let mut lights = vec![false; light_count];
+        let mut joltages = vec![0; light_count];
+        for button in &buttons {
+            let toggled = rng.chance(0.5);
+            let presses = rng.range(0..=10);
+
+            for &idx in button {
+                lights[idx] ^= toggled;
+                joltages[idx] += presses;
+            }
+        }
+
+        input.push('[');
+        input.extend(lights.iter().map(|&on| if on { '#' } else { '.' }));
+        input.push(']');
+
+        for button in &buttons {
+            let indexes: Vec<String> = button.iter().map(|idx| idx.to_string()).collect();
+            write!(input, " ({})", indexes.join(",")).unwrap();
+        }
+
+        let joltages: Vec<String> = joltages.iter().map(|j| j.to_string()).collect();
+        writeln!(input, " {{{}}}", joltages.join(",")).unwrap();
+    }
+
+    input
+}
+
+/// Directed acyclic graph of devices, with `svr` first, `out` last, and `you`, `dac` and `fft`
+/// somewhere in between.
+pub fn day11(rng: &mut Rng, size: usize) -> String {
+    let special_nodes = [SVR_NODE, YOU_NODE, DAC_NODE, FFT_NODE, OUT_NODE];
+
+    // Three-letter names run out eventually, so cap the number of devices
+    let device_count = size.min(10_000);
+    let mut names: HashSet<String> = special_nodes.iter().map(|n| n.to_string()).collect();
+    let mut nodes: Vec<String> = Vec::with_capacity(device_count + special_nodes.len());
+
+    while nodes.len() < device_count {
+        let name: String = (0..3)
+            .map(|_| char::from(b'a' + rng.below(26) as u8))
+            .collect();
+
+        if names.insert(name.clone()) {
+            nodes.push(name);
+        }
+    }
+
+    for special_node in [YOU_NODE, DAC_NODE, FFT_NODE] {
+        let idx = rng.index(nodes.len() + 1);
+        nodes.insert(idx, special_node.to_string());
+    }
+
+    nodes.insert(0, SVR_NODE.to_string());
+    nodes.push(OUT_NODE.to_string());
+
+    // Devices only connect to later devices, so there are no loops
+    let mut lines: Vec<String> = Vec::with_capacity(nodes.len() - 1);
+    for (idx, node) in nodes.iter().enumerate().take(nodes.len() - 1) {
+        let later_count = (nodes.len() - idx - 1) as u64;
+        let output_count = rng.range(1..=later_count.min(3));
+
+        let mut outputs: Vec<&str> = Vec::with_capacity(output_count as usize);
+        while outputs.len() < output_count as usize {
+            // Favour nearby devices, so paths are long rather than jumping straight to the end
+            let window = rng.range(1..=8);
+            let offset = 1 + rng.below(later_count.min(window)) as usize;
+            let output = nodes[idx + offset].as_str();
+
+            if !outputs.contains(&output) {
+                outputs.push(output);
+            }
+        }
+
+        lines.push(format!("{}: {}", node, outputs.join(" ")));
+    }
+
+    rng.shuffle(&mut lines);
+
+    let mut input = lines.join("\n");
+    input.push('\n');
+    input
+}
+
+/// Six 3x3 present shapes, then trees whose present counts are near the number that fit.
+pub fn day12(rng: &mut Rng, size: usize) -> String {
+    const PRESENT_COUNT: usize = 6;
+
+    let mut input = String::new();
+
+    for present_idx in 0..PRESENT_COUNT {
+        writeln!(input, "{}:", present_idx).unwrap();
+
+        // The centre is always filled, so no shape is empty
+        for row in 0..3 {
+            for col in 0..3 {
+                let filled = (row, col) == (1, 1) || rng.chance(0.7);
+                input.push(if filled { '#' } else { '.' });
+            }
+
+            input.push('\n');
+        }
+
+        input.push('\n');
+    }
+
+    for _ in 0..size {
+        let width = rng.range(3..=50);
+        let height = rng.range(3..=50);
+        let capacity = (width / 3) * (height / 3);
+
+        let mut present_counts = [0; PRESENT_COUNT];
+        for _ in 0..rng.range(capacity.saturating_sub(2)..=capacity + 2) {
+            present_counts[rng.index(PRESENT_COUNT)] += 1;
+        }
+
+        let present_counts: Vec<String> = present_counts.iter().map(|c| c.to_string()).collect();
+        writeln!(input, "{}x{}: {}", width, height, present_counts.join(" ")).unwrap();
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use advent_of_code_common::generator::Rng;
+
+    use crate::runner::DAYS;
+
+    #[test]
+    fn generated_inputs_are_solvable() {
+        for day in DAYS {
+            let generate = day.generate.expect("Every day has a generator");
+
+            for seed in 0..20 {
+                for size in [1, 2, 5] {
+                    let mut rng = Rng::new(seed);
+                    let input = generate(&mut rng, size);
+
+                    let parsed = (day.parse)(&input).unwrap_or_else(|e| {
+                        panic!(
+                            "Day {} rejected generated input:\n{}\n{}",
+                            day.number, input, e
+                        )
+                    });
+
+                    for part in 1..=day.parts {
+                        parsed.solve(part);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_input() {
+        for day in DAYS {
+            let generate = day.generate.unwrap();
+            let mut a = Rng::new(1);
+            let mut b = Rng::new(1);
+
+            assert_eq!(generate(&mut a, 10), generate(&mut b, 10));
+        }
+    }
+}
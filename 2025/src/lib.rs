@@ -1,12 +1,17 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+//! Solutions for Advent of Code 2025.
 
-/// Helper for creating an iterator over the lines in a file.
-pub fn read_lines(input_path: &str) -> impl Iterator<Item = String> {
-    let file = File::open(input_path).expect("Unable to open input file");
-    let reader = BufReader::new(file);
-
-    reader.lines().filter_map(|l| l.ok())
-}
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod generator;
+pub mod reference;
+pub mod runner;
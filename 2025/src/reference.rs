@@ -0,0 +1,434 @@
+//! Naive reference solvers for days that rely on clever shortcuts, used to check those days with
+//! differential testing.
+//!
+//! These parse the raw input themselves and do things the slow, obvious way (e.g. turning the dial
+//! one click at a time), so they're only practical on small generated inputs.
+
+use std::collections::HashSet;
+
+/// Reference solver for one part of a day, taking the raw input and returning the answer.
+pub struct ReferenceSolver {
+    pub day: u8,
+    pub part: usize,
+    pub solve: fn(&str) -> String,
+}
+
+/// Every reference solver, in day and part order.
+pub const REFERENCE_SOLVERS: &[ReferenceSolver] = &[
+    ReferenceSolver {
+        day: 1,
+        part: 1,
+        solve: day1_part1,
+    },
+    ReferenceSolver {
+        day: 1,
+        part: 2,
+        solve: day1_part2,
+    },
+    ReferenceSolver {
+        day: 2,
+        part: 1,
+        solve: day2_part1,
+    },
+    ReferenceSolver {
+        day: 2,
+        part: 2,
+        solve: day2_part2,
+    },
+    ReferenceSolver {
+        day: 3,
+        part: 1,
+        solve: day3_part1,
+    },
+    ReferenceSolver {
+        day: 3,
+        part: 2,
+        solve: day3_part2,
+    },
+    ReferenceSolver {
+        day: 5,
+        part: 1,
+        solve: day5_part1,
+    },
+    ReferenceSolver {
+        day: 5,
+        part: 2,
+        solve: day5_part2,
+    },
+    ReferenceSolver {
+        day: 7,
+        part: 1,
+        solve: day7_part1,
+    },
+    ReferenceSolver {
+        day: 7,
+        part: 2,
+        solve: day7_part2,
+    },
+];
+
+/// Turns the dial one click at a time, calling `on_click` with each position it passes through.
+fn turn_dial(input: &str, mut on_click: impl FnMut(u64), mut on_rotation_end: impl FnMut(u64)) {
+    let mut position = 50;
+
+    for line in input.lines().map(str::trim).take_while(|l| !l.is_empty()) {
+        let (direction, distance) = line.split_at(1);
+        let distance: u64 = distance.parse().unwrap();
+
+        for _ in 0..distance {
+            position = match direction {
+                "L" => (position + 99) % 100,
+                _ => (position + 1) % 100,
+            };
+
+            on_click(position);
+        }
+
+        on_rotation_end(position);
+    }
+}
+
+fn day1_part1(input: &str) -> String {
+    let mut zeroes = 0;
+    turn_dial(input, |_| {}, |position| zeroes += (position == 0) as u64);
+    zeroes.to_string()
+}
+
+fn day1_part2(input: &str) -> String {
+    let mut zeroes = 0;
+    turn_dial(input, |position| zeroes += (position == 0) as u64, |_| {});
+    zeroes.to_string()
+}
+
+/// Every ID in every range, in input order.
+fn day2_ids(input: &str) -> impl Iterator<Item = u64> + '_ {
+    let line = input.lines().next().unwrap_or_default();
+
+    line.split(',')
+        .filter(|r| !r.trim().is_empty())
+        .flat_map(|id_range| {
+            let (start, end) = id_range.split_once('-').unwrap();
+            start.trim().parse::<u64>().unwrap()..=end.trim().parse::<u64>().unwrap()
+        })
+}
+
+/// Whether the ID is made of a chunk repeated `count` times.
+fn is_repeated(id: u64, count: usize) -> bool {
+    let digits = id.to_string();
+    digits.len().is_multiple_of(count) && digits == digits[..digits.len() / count].repeat(count)
+}
+
+fn day2_part1(input: &str) -> String {
+    day2_ids(input)
+        .filter(|&id| is_repeated(id, 2))
+        .map(u128::from)
+        .sum::<u128>()
+        .to_string()
+}
+
+fn day2_part2(input: &str) -> String {
+    let invalid_ids: HashSet<u64> = day2_ids(input)
+        .filter(|&id| (2..=id.to_string().len()).any(|count| is_repeated(id, count)))
+        .collect();
+
+    invalid_ids
+        .into_iter()
+        .map(u128::from)
+        .sum::<u128>()
+        .to_string()
+}
+
+fn day3_banks(input: &str) -> impl Iterator<Item = Vec<u64>> + '_ {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|l| l.chars().map(|c| c.to_digit(10).unwrap() as u64).collect())
+}
+
+fn day3_part1(input: &str) -> String {
+    day3_banks(input)
+        .map(|digits| {
+            // Try every pair of batteries
+            let mut best = 0;
+            for i in 0..digits.len() {
+                for j in (i + 1)..digits.len() {
+                    best = best.max(digits[i] * 10 + digits[j]);
+                }
+            }
+
+            best
+        })
+        .sum::<u64>()
+        .to_string()
+}
+
+fn day3_part2(input: &str) -> String {
+    const BATTERY_COUNT: usize = 12;
+
+    day3_banks(input)
+        .map(|digits| {
+            // best[i][k] is the largest number made from k batteries at or after index i,
+            // considering both taking and skipping each battery rather than picking greedily
+            let n = digits.len();
+            let mut best = vec![vec![None; BATTERY_COUNT + 1]; n + 1];
+            for row in &mut best {
+                row[0] = Some(0_u64);
+            }
+
+            for i in (0..n).rev() {
+                for k in 1..=BATTERY_COUNT {
+                    let skip = best[i + 1][k];
+                    let take =
+                        best[i + 1][k - 1].map(|rest| digits[i] * 10_u64.pow(k as u32 - 1) + rest);
+
+                    best[i][k] = skip.max(take);
+                }
+            }
+
+            best[0][BATTERY_COUNT].unwrap()
+        })
+        .sum::<u64>()
+        .to_string()
+}
+
+/// Fresh ID ranges and available IDs.
+fn day5_database(input: &str) -> (Vec<(u64, u64)>, Vec<u64>) {
+    let mut lines = input.lines().map(str::trim).skip_while(|l| l.is_empty());
+
+    let id_ranges = lines
+        .by_ref()
+        .take_while(|l| !l.is_empty())
+        .map(|l| {
+            let (start, end) = l.split_once('-').unwrap();
+            (start.parse().unwrap(), end.parse().unwrap())
+        })
+        .collect();
+
+    let ids = lines
+        .flat_map(|l| l.split_whitespace())
+        .map(|id| id.parse().unwrap())
+        .collect();
+
+    (id_ranges, ids)
+}
+
+fn day5_part1(input: &str) -> String {
+    let (id_ranges, ids) = day5_database(input);
+
+    ids.iter()
+        .filter(|&&id| {
+            id_ranges
+                .iter()
+                .any(|&(start, end)| (start..=end).contains(&id))
+        })
+        .count()
+        .to_string()
+}
+
+fn day5_part2(input: &str) -> String {
+    let (id_ranges, _) = day5_database(input);
+
+    let fresh_ids: HashSet<u64> = id_ranges
+        .iter()
+        .flat_map(|&(start, end)| start..=end)
+        .collect();
+
+    fresh_ids.len().to_string()
+}
+
+fn day7_grid(input: &str) -> Vec<Vec<char>> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|l| l.chars().collect())
+        .collect()
+}
+
+fn day7_start(grid: &[Vec<char>]) -> Option<(usize, usize)> {
+    grid.iter()
+        .enumerate()
+        .find_map(|(row, cells)| cells.iter().position(|&c| c == 'S').map(|col| (row, col)))
+}
+
+/// Positions a beam moves to from `(row, col)`: straight down, or to both sides of a splitter.
+fn day7_next(grid: &[Vec<char>], row: usize, col: usize) -> Vec<(usize, usize)> {
+    if row + 1 == grid.len() {
+        return Vec::new();
+    }
+
+    if grid[row + 1][col] != '^' {
+        return vec![(row + 1, col)];
+    }
+
+    [col.checked_sub(1), Some(col + 1)]
+        .into_iter()
+        .flatten()
+        .filter(|&c| c < grid[row + 1].len())
+        .map(|c| (row + 1, c))
+        .collect()
+}
+
+fn day7_part1(input: &str) -> String {
+    let grid = day7_grid(input);
+    let Some(start) = day7_start(&grid) else {
+        return "0".to_string();
+    };
+
+    // Follow every beam, counting the distinct splitters that get hit
+    let mut visited = HashSet::new();
+    let mut splitters_hit = HashSet::new();
+    let mut beams = vec![start];
+
+    while let Some((row, col)) = beams.pop() {
+        if !visited.insert((row, col)) {
+            continue;
+        }
+
+        if row + 1 < grid.len() && grid[row + 1][col] == '^' {
+            splitters_hit.insert((row + 1, col));
+        }
+
+        beams.extend(day7_next(&grid, row, col));
+    }
+
+    splitters_hit.len().to_string()
+}
+
+fn day7_part2(input: &str) -> String {
+    let grid = day7_grid(input);
+    let Some(start) = day7_start(&grid) else {
+        return "0".to_string();
+    };
+
+    // Walk every possible path separately, counting the ones that reach the bottom
+    fn count_paths(grid: &[Vec<char>], row: usize, col: usize) -> u64 {
+        if row + 1 == grid.len() {
+            return 1;
+        }
+
+        day7_next(grid, row, col)
+            .into_iter()
+            .map(|(row, col)| count_paths(grid, row, col))
+            .sum()
+    }
+
+    count_paths(&grid, start.0, start.1).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use advent_of_code_common::property::{self, Config, Outcome};
+
+    use super::*;
+    use crate::{day1, runner::YEAR};
+
+    #[test]
+    fn matches_reference_solvers() {
+        for reference in REFERENCE_SOLVERS {
+            let day = YEAR.find_day(reference.day).unwrap();
+            let generate = day.generate.unwrap();
+
+            let result = property::check(
+                generate,
+                Config::default(),
+                property::check_against_reference(day, reference.part, reference.solve),
+            );
+
+            match result {
+                Ok(checked) => assert!(checked > 0, "Day {} had no valid inputs", day.number),
+                Err(counterexample) => panic!("{}", counterexample),
+            }
+        }
+    }
+
+    #[test]
+    fn day1_streaming_matches_step_logic() {
+        let generate = YEAR.find_day(1).unwrap().generate.unwrap();
+
+        let result = property::check(generate, Config::default(), |input| {
+            let Ok(counts) = day1::count_zeroes(input.as_bytes(), day1::Dial::default()) else {
+                return Outcome::Discard;
+            };
+
+            let answers = (counts.landed.to_string(), counts.passes.to_string());
+            let expected = (day1_part1(input), day1_part2(input));
+            if answers == expected {
+                Outcome::Pass
+            } else {
+                Outcome::Mismatch(format!(
+                    "Streamed {:?}, but expected {:?}",
+                    answers, expected
+                ))
+            }
+        });
+
+        if let Err(counterexample) = result {
+            panic!("{}", counterexample);
+        }
+    }
+
+    #[test]
+    fn day1_histogram_matches_step_logic() {
+        let generate = YEAR.find_day(1).unwrap().generate.unwrap();
+
+        let result = property::check(generate, Config::default(), |input| {
+            let Ok(exports) = day1::export(input) else {
+                return Outcome::Discard;
+            };
+            let positions = exports.iter().find(|e| e.name == "positions").unwrap();
+
+            let mut expected = vec![0_u64; 100];
+            turn_dial(input, |position| expected[position as usize] += 1, |_| {});
+            let expected: Vec<String> = expected
+                .iter()
+                .enumerate()
+                .map(|(position, visits)| format!("{},{}", position, visits))
+                .collect();
+
+            if positions.rows == expected {
+                Outcome::Pass
+            } else {
+                Outcome::Mismatch(format!(
+                    "Exported {:?}, but expected {:?}",
+                    positions.rows, expected
+                ))
+            }
+        });
+
+        if let Err(counterexample) = result {
+            panic!("{}", counterexample);
+        }
+    }
+
+    #[test]
+    fn reference_solvers_match_examples() {
+        let examples = [
+            (
+                day1_part2 as fn(&str) -> String,
+                "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n",
+                "6",
+            ),
+            (
+                day2_part2,
+                "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124\n",
+                "4174379265",
+            ),
+            (
+                day3_part2,
+                "987654321111111\n811111111111119\n234234234234278\n818181911112111\n",
+                "3121910778619",
+            ),
+            (
+                day5_part2,
+                "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32\n",
+                "14",
+            ),
+        ];
+
+        for (solve, input, expected) in examples {
+            assert_eq!(solve(input), expected);
+        }
+    }
+}
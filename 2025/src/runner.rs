@@ -0,0 +1,35 @@
+//! Registers this year's days with the shared runner.
+
+use advent_of_code_common::runner::{self, Day, Year, day};
+
+use crate::*;
+
+/// All days that have been solved so far, in order.
+pub const DAYS: &[Day] = &[
+    day::<day1::Day1>(1)
+        .with_generator(generator::day1)
+        .with_streamer(day1::stream)
+        .with_exporter(day1::export)
+        .with_tool(day1::LOCK_TOOL),
+    day::<day2::Day2>(2).with_generator(generator::day2),
+    day::<day3::Day3>(3).with_generator(generator::day3),
+    day::<day4::Day4>(4).with_generator(generator::day4),
+    day::<day5::Day5>(5).with_generator(generator::day5),
+    day::<day6::Day6>(6).with_generator(generator::day6),
+    day::<day7::Day7>(7).with_generator(generator::day7),
+    day::<day8::Day8>(8).with_generator(generator::day8),
+    day::<day9::Day9>(9).with_generator(generator::day9),
+    day::<day10::Day10>(10).with_generator(generator::day10),
+    day::<day11::Day11>(11).with_generator(generator::day11),
+    day::<day12::Day12>(12).with_generator(generator::day12),
+];
+
+pub const YEAR: Year = Year {
+    number: 2025,
+    days: DAYS,
+};
+
+/// Shared `main` for the individual `dayN` binaries.
+pub fn day_main(day_number: u8) {
+    runner::day_main(&YEAR, day_number);
+}
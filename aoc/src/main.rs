@@ -0,0 +1,585 @@
+use std::{env, io::ErrorKind, path::Path, process};
+
+use advent_of_code_common::{
+    bench,
+    error::InputError,
+    export::{self, EXPORTS_DIR},
+    fetch,
+    generator::Rng,
+    history::{self, DEFAULT_THRESHOLD_PERCENT, HISTORY_PATH},
+    ledger::LedgerSession,
+    memory::CountingAllocator,
+    parallel,
+    runner::*,
+    scaffold,
+    tool::{Tool, ToolError},
+};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Every year with solutions, in order. The last one is used unless `--year` is given.
+const YEARS: &[&Year] = &[&advent_of_code_2025::runner::YEAR];
+
+const USAGE: &str = "\
+Usage:
+  aoc run [--year YEAR] <day> [options] <input>    (use '-' to read the input from stdin)
+  aoc run [--year YEAR] all [options] <input-dir>
+  aoc compare [<base-commit> [<new-commit>]] [--threshold PERCENT]
+      Compares median timings saved with --save-history (defaults to the last two commits),
+      and exits with an error if any day/part slowed down by more than the threshold (default 10%)
+  aoc fetch [--year YEAR] [--base-url URL] <day>
+      Downloads a day's input into inputs/<year>/dayNN.txt using the session token in AOC_SESSION,
+      unless it's already cached. The base URL can also be set with AOC_BASE_URL
+  aoc new <year> <day>
+      Generates the skeleton for a new day and registers it with the runner, Cargo.toml and README.
+      For a new year, also creates its crate and adds it to the workspace and to aoc's YEARS
+  aoc gen [--year YEAR] <day> [--seed S] [--size N]
+      Prints a random input for a day, e.g. for stress-testing (defaults: seed 0, size 10)
+  aoc export [--year YEAR] <day> [--out DIR] <input>
+      Writes CSV exports of a day's intermediate state into DIR/<year>/ (default: exports)";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(|a| a.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("compare") => compare(&args[1..]),
+        Some("fetch") => fetch_input(&args[1..]),
+        Some("new") => new_day(&args[1..]),
+        Some("gen") => generate_input(&args[1..]),
+        Some("export") => export_day(&args[1..]),
+        Some(name) => run_tool(name, &args[1..]),
+        None => exit_with_usage(None),
+    }
+}
+
+fn exit_with_usage(message: Option<&str>) -> ! {
+    if let Some(message) = message {
+        eprintln!("{}", message);
+    }
+
+    eprintln!("{}", USAGE);
+    for tool in all_tools() {
+        eprintln!("{}", tool.usage());
+    }
+
+    eprintln!("\n{}", OPTIONS_USAGE);
+    process::exit(1);
+}
+
+fn find_year(year_arg: &str) -> &'static Year {
+    YEARS
+        .iter()
+        .find(|y| y.number.to_string() == year_arg)
+        .unwrap_or_else(|| exit_with_usage(Some(&format!("Unknown year '{}'", year_arg))))
+}
+
+fn run(args: &[String]) {
+    let mut day_arg: Option<&str> = None;
+    let mut input_path: Option<&str> = None;
+    let mut year = YEARS[YEARS.len() - 1];
+    let mut options = RunOptions::default();
+
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        if arg == "--year" {
+            let year_arg = args_iter
+                .next()
+                .unwrap_or_else(|| exit_with_usage(Some("Missing value for --year")));
+
+            year = find_year(year_arg);
+            continue;
+        }
+
+        match options.parse_flag(arg, &mut args_iter) {
+            Ok(true) => {}
+            Ok(false) if day_arg.is_none() => day_arg = Some(arg),
+            Ok(false) if input_path.is_none() => input_path = Some(arg),
+            Ok(false) => exit_with_usage(Some(&format!("Unexpected argument '{}'", arg))),
+            Err(message) => exit_with_usage(Some(&message)),
+        }
+    }
+
+    if let Err(message) = options.validate() {
+        exit_with_usage(Some(&message));
+    }
+
+    options.apply();
+
+    let day_arg = day_arg.unwrap_or_else(|| exit_with_usage(Some("No day specified")));
+    let input_path = input_path.unwrap_or_else(|| exit_with_usage(Some("No input file specified")));
+
+    let mut ledger = options.ledger_mode.map(open_ledger);
+
+    let succeeded = if day_arg == "all" {
+        run_all(year, Path::new(input_path), &options, ledger.as_mut())
+    } else {
+        run_single(year, day_arg, &options, input_path, ledger.as_mut())
+    };
+
+    // Save any recorded answers before exiting, even if some days failed
+    if let Some(ledger) = ledger {
+        finish_ledger(ledger);
+    }
+
+    if !succeeded {
+        process::exit(1);
+    }
+}
+
+fn run_single(
+    year: &Year,
+    day_arg: &str,
+    options: &RunOptions,
+    input_path: &str,
+    ledger: Option<&mut LedgerSession>,
+) -> bool {
+    let day = day_arg
+        .parse::<u8>()
+        .ok()
+        .and_then(|d| year.find_day(d))
+        .unwrap_or_else(|| exit_with_usage(Some(&format!("Unknown day '{}'", day_arg))));
+
+    if options.part.is_some_and(|p| p > day.parts) {
+        eprintln!("Day {} has no part {}", day.number, options.part.unwrap());
+        process::exit(1);
+    }
+
+    if options.is_markdown_table() {
+        println!("{}", bench::MARKDOWN_HEADER);
+    }
+
+    if let Err(e) = run_day(year, day, options, input_path, ledger) {
+        report_error(input_path, e);
+        return false;
+    }
+
+    true
+}
+
+fn run_all(
+    year: &Year,
+    input_dir: &Path,
+    options: &RunOptions,
+    mut ledger: Option<&mut LedgerSession>,
+) -> bool {
+    if !input_dir.is_dir() {
+        eprintln!("Input directory '{}' not found", input_dir.display());
+        process::exit(1);
+    }
+
+    if options.is_markdown_table() {
+        println!("{}", bench::MARKDOWN_HEADER);
+    }
+
+    let day_headers = options.prints_day_headers();
+    let day_inputs: Vec<(&Day, Option<String>)> = year
+        .days
+        .iter()
+        .map(|day| (day, find_input_file(input_dir, day.number)))
+        .collect();
+
+    let solve = |(day, input_path): &(&Day, Option<String>)| {
+        input_path
+            .as_ref()
+            .map(|input_path| solve_day(day, options, input_path))
+    };
+
+    // Days are solved in parallel, except when benchmarking so they don't skew each other's
+    // timings, then printed in order
+    let outputs = if options.bench_iterations.is_some() {
+        day_inputs.iter().map(solve).collect()
+    } else {
+        parallel::map(&day_inputs, solve)
+    };
+
+    let mut failed = false;
+
+    for (idx, ((day, input_path), output)) in day_inputs.iter().zip(outputs).enumerate() {
+        if idx > 0 && day_headers {
+            println!();
+        }
+
+        match (input_path, output) {
+            (Some(input_path), Some(output)) => {
+                if let Err(e) = print_day(
+                    year,
+                    day,
+                    options,
+                    input_path,
+                    output,
+                    ledger.as_deref_mut(),
+                ) {
+                    report_error(input_path, e);
+                    failed = true;
+                }
+            }
+            _ if !day_headers => {}
+            _ => {
+                println!("----- Day {} -----", day.number);
+                println!("No input file found, skipping");
+            }
+        }
+    }
+
+    !failed
+}
+
+fn compare(args: &[String]) {
+    let mut commit_args: Vec<&str> = Vec::new();
+    let mut threshold_percent = DEFAULT_THRESHOLD_PERCENT;
+
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "--threshold" => {
+                let threshold_str = args_iter
+                    .next()
+                    .unwrap_or_else(|| exit_with_usage(Some("Missing value for --threshold")));
+
+                threshold_percent = threshold_str
+                    .trim_end_matches('%')
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|t| *t >= 0.0)
+                    .unwrap_or_else(|| {
+                        exit_with_usage(Some(&format!("Invalid threshold '{}'", threshold_str)))
+                    });
+            }
+            value if commit_args.len() < 2 => commit_args.push(value),
+            value => exit_with_usage(Some(&format!("Unexpected argument '{}'", value))),
+        }
+    }
+
+    let records = history::load(Path::new(HISTORY_PATH)).unwrap_or_else(|e| {
+        match e {
+            InputError::Io(e) => {
+                eprintln!("Unable to read timing history '{}': {}", HISTORY_PATH, e)
+            }
+            InputError::Parse(e) => eprintln!("{}", e.with_file(HISTORY_PATH).render()),
+        }
+
+        process::exit(1);
+    });
+
+    let commits = history::commits(&records);
+    let (base_commit, new_commit) = match (commit_args.as_slice(), commits.as_slice()) {
+        ([base, new], _) => (*base, *new),
+        ([base], [.., latest]) => (*base, *latest),
+        ([], [.., previous, latest]) => (*previous, *latest),
+        _ => {
+            eprintln!("Not enough commits in '{}' to compare", HISTORY_PATH);
+            process::exit(1);
+        }
+    };
+
+    let comparisons = history::compare(&records, base_commit, new_commit);
+    if comparisons.is_empty() {
+        eprintln!(
+            "No days were measured at both '{}' and '{}'",
+            base_commit, new_commit
+        );
+        process::exit(1);
+    }
+
+    println!(
+        "Comparing {} -> {} (threshold {}%)",
+        base_commit, new_commit, threshold_percent
+    );
+
+    for comparison in &comparisons {
+        println!(
+            "{}",
+            history::format_comparison(comparison, threshold_percent)
+        );
+    }
+
+    let regressions = comparisons
+        .iter()
+        .filter(|c| c.is_regression(threshold_percent))
+        .count();
+
+    if regressions > 0 {
+        println!("{} regression(s) found", regressions);
+        process::exit(1);
+    }
+
+    println!("No regressions found");
+}
+
+fn fetch_input(args: &[String]) {
+    let mut year = YEARS[YEARS.len() - 1].number;
+    let mut base_url = fetch::base_url();
+    let mut day_arg: Option<&str> = None;
+
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        let mut flag_value = || {
+            args_iter
+                .next()
+                .unwrap_or_else(|| exit_with_usage(Some(&format!("Missing value for {}", arg))))
+        };
+
+        match arg.as_str() {
+            "--year" => year = find_year(flag_value()).number,
+            "--base-url" => base_url = flag_value().to_string(),
+            value if day_arg.is_none() => day_arg = Some(value),
+            value => exit_with_usage(Some(&format!("Unexpected argument '{}'", value))),
+        }
+    }
+
+    let day_arg = day_arg.unwrap_or_else(|| exit_with_usage(Some("No day specified")));
+    let day = day_arg
+        .parse::<u8>()
+        .ok()
+        .filter(|d| (1..=25).contains(d))
+        .unwrap_or_else(|| exit_with_usage(Some(&format!("Invalid day '{}'", day_arg))));
+
+    // The session token is only needed if the input isn't cached yet
+    let cache_dir = Path::new(fetch::INPUTS_DIR);
+    let cached_path = fetch::cached_input_path(cache_dir, year, day);
+    if cached_path.is_file() {
+        println!("Input is already cached at '{}'", cached_path.display());
+        return;
+    }
+
+    let result = fetch::session_token().and_then(|session| {
+        fetch::fetch_input(
+            fetch::fetcher_for(&base_url).as_ref(),
+            &base_url,
+            &session,
+            cache_dir,
+            year,
+            day,
+        )
+    });
+
+    match result {
+        Ok((path, _)) => println!("Downloaded input to '{}'", path.display()),
+        Err(e) => {
+            eprintln!("Unable to fetch input for day {}: {}", day, e);
+            process::exit(1);
+        }
+    }
+}
+
+fn new_day(args: &[String]) {
+    let [year_arg, day_arg] = args else {
+        exit_with_usage(Some("Expected a year and a day"));
+    };
+
+    let year = year_arg
+        .parse::<u16>()
+        .unwrap_or_else(|_| exit_with_usage(Some(&format!("Invalid year '{}'", year_arg))));
+    let day = day_arg
+        .parse::<u8>()
+        .ok()
+        .filter(|d| (1..=25).contains(d))
+        .unwrap_or_else(|| exit_with_usage(Some(&format!("Invalid day '{}'", day_arg))));
+
+    let mut changed = Vec::new();
+    let crate_dir = match scaffold::find_year_dir(year) {
+        Ok(crate_dir) => crate_dir,
+        Err(e) if e.kind() == ErrorKind::NotFound => scaffold::find_workspace_dir()
+            .and_then(|workspace_dir| {
+                changed.extend(scaffold::scaffold_year(&workspace_dir, year)?);
+                Ok(workspace_dir.join(year.to_string()))
+            })
+            .unwrap_or_else(|e| {
+                eprintln!("Unable to create the crate for {}: {}", year, e);
+                process::exit(1);
+            }),
+        Err(e) => {
+            eprintln!("Unable to create day {}: {}", day, e);
+            process::exit(1);
+        }
+    };
+
+    let created_year = !changed.is_empty();
+    changed.extend(
+        scaffold::scaffold_day(&crate_dir, year, day).unwrap_or_else(|e| {
+            eprintln!("Unable to create day {}: {}", day, e);
+            process::exit(1);
+        }),
+    );
+
+    if created_year {
+        println!("Created the crate for {} and day {}:", year, day);
+    } else {
+        println!("Created day {} for {}:", day, year);
+    }
+
+    for path in changed {
+        println!("- {}", path.display());
+    }
+}
+
+fn generate_input(args: &[String]) {
+    let mut year = YEARS[YEARS.len() - 1];
+    let mut seed = 0;
+    let mut size = 10;
+    let mut day_arg: Option<&str> = None;
+
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        let mut flag_value = || {
+            args_iter
+                .next()
+                .unwrap_or_else(|| exit_with_usage(Some(&format!("Missing value for {}", arg))))
+        };
+
+        match arg.as_str() {
+            "--year" => year = find_year(flag_value()),
+            "--seed" => {
+                let seed_str = flag_value();
+                seed = seed_str.parse::<u64>().unwrap_or_else(|_| {
+                    exit_with_usage(Some(&format!("Invalid seed '{}'", seed_str)))
+                });
+            }
+            "--size" => {
+                let size_str = flag_value();
+                size = size_str
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&s| s > 0)
+                    .unwrap_or_else(|| {
+                        exit_with_usage(Some(&format!("Invalid size '{}'", size_str)))
+                    });
+            }
+            value if day_arg.is_none() => day_arg = Some(value),
+            value => exit_with_usage(Some(&format!("Unexpected argument '{}'", value))),
+        }
+    }
+
+    let day_arg = day_arg.unwrap_or_else(|| exit_with_usage(Some("No day specified")));
+    let day = day_arg
+        .parse::<u8>()
+        .ok()
+        .and_then(|d| year.find_day(d))
+        .unwrap_or_else(|| exit_with_usage(Some(&format!("Unknown day '{}'", day_arg))));
+
+    let Some(generate) = day.generate else {
+        eprintln!("Day {} has no input generator", day.number);
+        process::exit(1);
+    };
+
+    print!("{}", generate(&mut Rng::new(seed), size));
+}
+
+fn export_day(args: &[String]) {
+    let mut year = YEARS[YEARS.len() - 1];
+    let mut exports_dir = EXPORTS_DIR.to_string();
+    let mut day_arg: Option<&str> = None;
+    let mut input_path: Option<&str> = None;
+
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        let mut flag_value = || {
+            args_iter
+                .next()
+                .unwrap_or_else(|| exit_with_usage(Some(&format!("Missing value for {}", arg))))
+        };
+
+        match arg.as_str() {
+            "--year" => year = find_year(flag_value()),
+            "--out" => exports_dir = flag_value().to_string(),
+            value if day_arg.is_none() => day_arg = Some(value),
+            value if input_path.is_none() => input_path = Some(value),
+            value => exit_with_usage(Some(&format!("Unexpected argument '{}'", value))),
+        }
+    }
+
+    let day_arg = day_arg.unwrap_or_else(|| exit_with_usage(Some("No day specified")));
+    let input_path = input_path.unwrap_or_else(|| exit_with_usage(Some("No input file specified")));
+    let day = day_arg
+        .parse::<u8>()
+        .ok()
+        .and_then(|d| year.find_day(d))
+        .unwrap_or_else(|| exit_with_usage(Some(&format!("Unknown day '{}'", day_arg))));
+
+    let Some(export) = day.export else {
+        eprintln!("Day {} has no exports", day.number);
+        process::exit(1);
+    };
+
+    let exports = advent_of_code_common::read_input(input_path)
+        .map_err(InputError::from)
+        .and_then(|input| Ok(export(&input)?))
+        .unwrap_or_else(|e| {
+            report_error(input_path, e);
+            process::exit(1);
+        });
+
+    let paths = export::write_exports(Path::new(&exports_dir), year.number, day.number, &exports)
+        .unwrap_or_else(|e| {
+            eprintln!("Unable to write exports for day {}: {}", day.number, e);
+            process::exit(1);
+        });
+
+    println!("Exported day {} for {}:", day.number, year.number);
+    for path in paths {
+        println!("- {}", path.display());
+    }
+}
+
+/// Tools registered by any day, in year and day order.
+fn all_tools() -> impl Iterator<Item = &'static Tool> {
+    YEARS
+        .iter()
+        .flat_map(|year| year.days)
+        .filter_map(|day| day.tool.as_ref())
+}
+
+fn run_tool(name: &str, args: &[String]) {
+    let mut year = YEARS[YEARS.len() - 1];
+    let mut tool_args: Vec<String> = Vec::new();
+
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        if arg == "--year" {
+            let year_arg = args_iter
+                .next()
+                .unwrap_or_else(|| exit_with_usage(Some("Missing value for --year")));
+
+            year = find_year(year_arg);
+        } else {
+            tool_args.push(arg.clone());
+        }
+    }
+
+    let tool = year.find_tool(name).unwrap_or_else(|| {
+        let message = match all_tools().any(|t| t.name == name) {
+            true => format!("No day in {} has the '{}' command", year.number, name),
+            false => format!("Unknown command '{}'", name),
+        };
+        exit_with_usage(Some(&message))
+    });
+
+    // The input comes last, so every other argument is an option for the tool
+    let input_path = tool_args
+        .pop()
+        .unwrap_or_else(|| exit_with_usage(Some("No input file specified")));
+
+    let input = advent_of_code_common::read_input(&input_path).unwrap_or_else(|e| {
+        report_error(&input_path, e.into());
+        process::exit(1);
+    });
+
+    match (tool.run)(&input, &tool_args) {
+        Ok(lines) => {
+            for line in lines {
+                println!("{}", line);
+            }
+        }
+        Err(ToolError::Usage(message)) => exit_with_usage(Some(&message)),
+        Err(ToolError::Input(e)) => {
+            report_error(&input_path, e.into());
+            process::exit(1);
+        }
+        Err(ToolError::Failed(message)) => {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+//! Benchmarking of the parse and solve stages of a day.
+
+use std::{
+    fmt,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{error::ParseError, memory, runner::Day};
+
+/// Default number of iterations for each stage.
+pub const DEFAULT_ITERATIONS: usize = 100;
+
+/// Header for the Markdown table produced by [`BenchReport::markdown_row`].
+pub const MARKDOWN_HEADER: &str = "\
+| Day | Parse | Part 1 | Part 2 | Peak Alloc |
+|---|---|---|---|---|";
+
+/// Summary statistics over the timings of one stage.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Calculates the statistics over a non-empty list of timings.
+    pub fn from_timings(mut timings: Vec<Duration>) -> Self {
+        assert!(!timings.is_empty(), "No timings to summarise");
+        timings.sort();
+
+        // Nearest-rank percentiles
+        let percentile = |p: usize| timings[(timings.len() * p).div_ceil(100).max(1) - 1];
+
+        Stats {
+            min: timings[0],
+            median: percentile(50),
+            p95: percentile(95),
+        }
+    }
+}
+
+/// Timings for one stage (parsing, or solving a part).
+#[derive(Clone, Debug)]
+pub struct StageReport {
+    pub name: String,
+    pub stats: Stats,
+    /// Peak heap usage above the baseline while running the stage, if allocations are tracked.
+    pub peak_alloc: Option<usize>,
+}
+
+/// Timings for all stages of a day.
+#[derive(Clone, Debug)]
+pub struct BenchReport {
+    pub day: u8,
+    pub iterations: usize,
+    pub parse: StageReport,
+    /// Timings for each part, or `None` for parts that weren't benchmarked.
+    pub parts: Vec<Option<StageReport>>,
+}
+
+impl BenchReport {
+    /// Formats the median timings as a row of [`MARKDOWN_HEADER`], to slot into the README.
+    pub fn markdown_row(&self) -> String {
+        let mut row = format!(
+            "| **{}** | {} |",
+            self.day,
+            format_duration(self.parse.stats.median)
+        );
+
+        for part_idx in 0..2 {
+            match self.parts.get(part_idx) {
+                Some(Some(stage)) => row += &format!(" {} |", format_duration(stage.stats.median)),
+                Some(None) => row += " - |",
+                None => row += " N/A |",
+            }
+        }
+
+        let peak_alloc = self
+            .stages()
+            .filter_map(|stage| stage.peak_alloc)
+            .max()
+            .map(format_bytes)
+            .unwrap_or_else(|| "-".to_string());
+        row += &format!(" {} |", peak_alloc);
+
+        row
+    }
+
+    fn stages(&self) -> impl Iterator<Item = &StageReport> {
+        std::iter::once(&self.parse).chain(self.parts.iter().flatten())
+    }
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Benchmark ({} iterations):", self.iterations)?;
+
+        for stage in self.stages() {
+            write!(
+                f,
+                "{:<8} min {:>10}, median {:>10}, p95 {:>10}",
+                format!("{}:", stage.name),
+                format_duration(stage.stats.min),
+                format_duration(stage.stats.median),
+                format_duration(stage.stats.p95),
+            )?;
+
+            if let Some(peak_alloc) = stage.peak_alloc {
+                write!(f, ", peak alloc {:>10}", format_bytes(peak_alloc))?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Benchmarks parsing and the requested part (or all parts) of a day.
+pub fn bench_day(
+    day: &Day,
+    input: &str,
+    part: Option<usize>,
+    iterations: usize,
+) -> Result<BenchReport, ParseError> {
+    let parsed_input = (day.parse)(input)?;
+
+    let parse = bench_stage("Parse", iterations, || {
+        black_box((day.parse)(black_box(input)).is_ok());
+    });
+
+    let parts = (1..=day.parts)
+        .map(|part_number| {
+            part.is_none_or(|p| p == part_number).then(|| {
+                bench_stage(&format!("Part {}", part_number), iterations, || {
+                    black_box(parsed_input.solve(part_number));
+                })
+            })
+        })
+        .collect();
+
+    Ok(BenchReport {
+        day: day.number,
+        iterations,
+        parse,
+        parts,
+    })
+}
+
+fn bench_stage(name: &str, iterations: usize, mut stage: impl FnMut()) -> StageReport {
+    let mut timings = Vec::with_capacity(iterations);
+    let mut peak_alloc = 0;
+
+    for _ in 0..iterations.max(1) {
+        let baseline = memory::reset_peak();
+
+        let start = Instant::now();
+        stage();
+        timings.push(start.elapsed());
+
+        peak_alloc = peak_alloc.max(memory::peak().saturating_sub(baseline));
+    }
+
+    StageReport {
+        name: name.to_string(),
+        stats: Stats::from_timings(timings),
+        peak_alloc: memory::is_tracking().then_some(peak_alloc),
+    }
+}
+
+/// Formats a duration with a unit suited to its size, e.g. `12.3 µs`.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+
+    if nanos < 1_000 {
+        format!("{} ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1} µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.1} ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2} s", nanos as f64 / 1e9)
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `4.2 KiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_use_nearest_rank() {
+        let timings = (1..=20).rev().map(Duration::from_millis).collect();
+        let stats = Stats::from_timings(timings);
+
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(10));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+
+        let single = Stats::from_timings(vec![Duration::from_micros(5)]);
+        assert_eq!(single.median, Duration::from_micros(5));
+        assert_eq!(single.p95, Duration::from_micros(5));
+    }
+
+    #[test]
+    fn formats_units() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999 ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.3 µs");
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1.50 s");
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(4300), "4.2 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,345 @@
+//! Dependency-free arbitrary-precision unsigned integers, for answers that can outgrow `u64`
+//! (e.g. path counts and products of large numbers).
+
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign},
+    str::FromStr,
+};
+
+/// Arbitrary-precision unsigned integer.
+#[derive(Clone, Default, PartialEq, Eq, Hash, Debug)]
+pub struct BigUint {
+    /// Base 2^32 digits, least significant first, with no trailing zero limbs (so zero is empty).
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub const fn zero() -> Self {
+        BigUint { limbs: Vec::new() }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Builds a number from its decimal digits, most significant first.
+    pub fn from_digits(digits: impl IntoIterator<Item = u8>) -> Self {
+        let mut number = BigUint::zero();
+        for digit in digits {
+            debug_assert!(digit < 10, "Invalid decimal digit {}", digit);
+            number.mul_add_small(10, digit as u32);
+        }
+
+        number
+    }
+
+    /// Converts to a `u64`, if the number is small enough.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.as_slice() {
+            [] => Some(0),
+            [low] => Some(*low as u64),
+            [low, high] => Some(((*high as u64) << 32) | *low as u64),
+            _ => None,
+        }
+    }
+
+    /// Calculates `self * multiplier + addend` in place.
+    fn mul_add_small(&mut self, multiplier: u32, addend: u32) {
+        let mut carry = addend as u64;
+        for limb in self.limbs.iter_mut() {
+            let value = (*limb as u64) * (multiplier as u64) + carry;
+            *limb = value as u32;
+            carry = value >> 32;
+        }
+
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+
+        self.normalise();
+    }
+
+    /// Divides in place, returning the remainder. Panics if `divisor` is 0.
+    pub fn div_rem_small(&mut self, divisor: u64) -> u64 {
+        let mut remainder = 0_u128;
+        for limb in self.limbs.iter_mut().rev() {
+            let value = (remainder << 32) | *limb as u128;
+            *limb = (value / divisor as u128) as u32;
+            remainder = value % divisor as u128;
+        }
+
+        self.normalise();
+        remainder as u64
+    }
+
+    fn normalise(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+}
+
+/// Error from parsing a [`BigUint`] that isn't a plain decimal number.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseBigUintError;
+
+impl Display for ParseBigUintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid digit found in string")
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = ParseBigUintError;
+
+    /// Parses decimal digits, with an optional leading `+` like the primitive integers.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix('+').unwrap_or(s);
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseBigUintError);
+        }
+
+        Ok(BigUint::from_digits(digits.bytes().map(|b| b - b'0')))
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        let mut number = BigUint {
+            limbs: vec![value as u32, (value >> 32) as u32],
+        };
+        number.normalise();
+        number
+    }
+}
+
+impl PartialEq<u64> for BigUint {
+    fn eq(&self, other: &u64) -> bool {
+        self.to_u64() == Some(*other)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl AddAssign<&BigUint> for BigUint {
+    fn add_assign(&mut self, rhs: &BigUint) {
+        if self.limbs.len() < rhs.limbs.len() {
+            self.limbs.resize(rhs.limbs.len(), 0);
+        }
+
+        let mut carry = 0_u64;
+        for (idx, limb) in self.limbs.iter_mut().enumerate() {
+            let value = *limb as u64 + rhs.limbs.get(idx).copied().unwrap_or(0) as u64 + carry;
+            *limb = value as u32;
+            carry = value >> 32;
+
+            if carry == 0 && idx >= rhs.limbs.len() {
+                break;
+            }
+        }
+
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+}
+
+impl AddAssign for BigUint {
+    fn add_assign(&mut self, rhs: BigUint) {
+        *self += &rhs;
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+
+    fn add(mut self, rhs: BigUint) -> Self::Output {
+        self += &rhs;
+        self
+    }
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, rhs: &BigUint) -> Self::Output {
+        let mut sum = self.clone();
+        sum += rhs;
+        sum
+    }
+}
+
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, rhs: &BigUint) -> Self::Output {
+        if self.is_zero() || rhs.is_zero() {
+            return BigUint::zero();
+        }
+
+        // Schoolbook multiplication, which is plenty fast for puzzle-sized numbers
+        let mut limbs = vec![0_u32; self.limbs.len() + rhs.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0_u64;
+            for (j, &b) in rhs.limbs.iter().enumerate() {
+                let value = (a as u64) * (b as u64) + limbs[i + j] as u64 + carry;
+                limbs[i + j] = value as u32;
+                carry = value >> 32;
+            }
+
+            limbs[i + rhs.limbs.len()] = carry as u32;
+        }
+
+        let mut product = BigUint { limbs };
+        product.normalise();
+        product
+    }
+}
+
+impl Mul for BigUint {
+    type Output = BigUint;
+
+    fn mul(self, rhs: BigUint) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+impl MulAssign<&BigUint> for BigUint {
+    fn mul_assign(&mut self, rhs: &BigUint) {
+        *self = &*self * rhs;
+    }
+}
+
+impl Sum for BigUint {
+    fn sum<I: Iterator<Item = BigUint>>(iter: I) -> Self {
+        iter.fold(BigUint::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<'a> Sum<&'a BigUint> for BigUint {
+    fn sum<I: Iterator<Item = &'a BigUint>>(iter: I) -> Self {
+        iter.fold(BigUint::zero(), |mut acc, x| {
+            acc += x;
+            acc
+        })
+    }
+}
+
+impl Product for BigUint {
+    fn product<I: Iterator<Item = BigUint>>(iter: I) -> Self {
+        iter.fold(BigUint::from(1), |acc, x| acc * x)
+    }
+}
+
+impl<'a> Product<&'a BigUint> for BigUint {
+    fn product<I: Iterator<Item = &'a BigUint>>(iter: I) -> Self {
+        iter.fold(BigUint::from(1), |mut acc, x| {
+            acc *= x;
+            acc
+        })
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const CHUNK_DIVISOR: u64 = 1_000_000_000;
+
+        // Split into 9 digit chunks, least significant first
+        let mut remaining = self.clone();
+        let mut chunks = Vec::new();
+        while !remaining.is_zero() {
+            chunks.push(remaining.div_rem_small(CHUNK_DIVISOR));
+        }
+
+        let mut digits = match chunks.pop() {
+            Some(most_significant) => most_significant.to_string(),
+            None => "0".to_string(),
+        };
+
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:09}", chunk));
+        }
+
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_and_multiplies_past_u64() {
+        let max = BigUint::from(u64::MAX);
+
+        assert_eq!(
+            (&max + &BigUint::from(1)).to_string(),
+            "18446744073709551616"
+        );
+        assert_eq!(
+            (&max * &max).to_string(),
+            "340282366920938463426481119284349108225"
+        );
+        assert_eq!(
+            [&max, &max, &max].into_iter().sum::<BigUint>().to_string(),
+            "55340232221128654845"
+        );
+        assert_eq!((&max * &BigUint::zero()), 0);
+        assert_eq!((&max + &BigUint::zero()).to_u64(), Some(u64::MAX));
+        assert_eq!((&max + &BigUint::from(1)).to_u64(), None);
+    }
+
+    #[test]
+    fn parses_digits_and_displays() {
+        let digits = "123456789012345678901234567890";
+        let number = BigUint::from_digits(digits.bytes().map(|b| b - b'0'));
+
+        assert_eq!(number.to_string(), digits);
+        assert_eq!(BigUint::from_digits([0, 0, 7]), 7);
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert_eq!(format!("{:>5}", BigUint::from(42)), "   42");
+        assert_eq!(format!("{:03}", BigUint::from(7)), "007");
+
+        assert_eq!("+12".parse::<BigUint>(), Ok(BigUint::from(12)));
+        assert!("".parse::<BigUint>().is_err());
+        assert!("-1".parse::<BigUint>().is_err());
+        assert!("1_000".parse::<BigUint>().is_err());
+    }
+
+    #[test]
+    fn divides_by_small_numbers() {
+        let mut number: BigUint = "123456789012345678901234567890".parse().unwrap();
+        let remainder = number.div_rem_small(u64::MAX);
+
+        // 123456789012345678901234567890 = 6692605942 * (2^64 - 1) + 14083847780529871560
+        assert_eq!(number, 6692605942);
+        assert_eq!(remainder, 14083847780529871560);
+    }
+
+    #[test]
+    fn compares_by_magnitude() {
+        let small = BigUint::from(u64::MAX);
+        let large = &small * &BigUint::from(2);
+
+        assert!(small < large);
+        let (two_limbs, one_limb) = (BigUint::from(1 << 40), BigUint::from(u32::MAX as u64));
+        assert!(two_limbs > one_limb);
+        assert_eq!(large.clone().max(small), large);
+    }
+}
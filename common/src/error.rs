@@ -0,0 +1,148 @@
+use std::{error::Error, fmt, io, str::FromStr};
+
+/// Error for malformed puzzle input, pointing at the offending token.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    /// Input file the error came from, if known.
+    pub file: Option<String>,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column number, counted in characters.
+    pub column: usize,
+    /// The offending token (may be empty if something was missing).
+    pub token: String,
+    /// Contents of the line containing the error, used for rendering diagnostics.
+    pub line_text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Creates an error for a token within a line of the input.
+    ///
+    /// The token should be a slice of the line so that its column can be worked out. Otherwise,
+    /// the error will point at the start of the line.
+    pub fn new(line_number: usize, line: &str, token: &str, message: impl Into<String>) -> Self {
+        let line_start = line.as_ptr() as usize;
+        let token_start = token.as_ptr() as usize;
+
+        let column = if token_start >= line_start && token_start <= line_start + line.len() {
+            line[..(token_start - line_start)].chars().count() + 1
+        } else {
+            1
+        };
+
+        ParseError {
+            file: None,
+            line: line_number,
+            column,
+            token: token.to_string(),
+            line_text: line.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Creates an error for something that was expected after the last line of the input.
+    pub fn end_of_input(line_number: usize, message: impl Into<String>) -> Self {
+        ParseError::new(line_number, "", "", message)
+    }
+
+    /// Attaches the name of the input file to the error.
+    pub fn with_file(mut self, file: &str) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+
+    /// Renders the error with the offending line and a caret underneath the token, e.g.:
+    ///
+    /// ```text
+    /// error: Unexpected direction 'X'
+    ///  --> input.txt:3:1
+    ///   |
+    /// 3 | X39
+    ///   | ^
+    /// ```
+    pub fn render(&self) -> String {
+        let line_number = self.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        let caret_count = self.token.chars().count().max(1);
+
+        format!(
+            "error: {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}",
+            self.message_with_token(),
+            gutter,
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column,
+            gutter,
+            line_number,
+            self.line_text,
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(caret_count),
+        )
+    }
+
+    fn message_with_token(&self) -> String {
+        if self.token.is_empty() {
+            self.message.clone()
+        } else {
+            format!("{} '{}'", self.message, self.token)
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}",
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column,
+            self.message_with_token()
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses a token within a line as a number, or returns an error pointing at the token.
+pub fn parse_number<T: FromStr>(
+    line_number: usize,
+    line: &str,
+    token: &str,
+) -> Result<T, ParseError> {
+    token
+        .parse::<T>()
+        .map_err(|_| ParseError::new(line_number, line, token, "Invalid number"))
+}
+
+/// Errors that can occur while reading and parsing puzzle input.
+#[derive(Debug)]
+pub enum InputError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Io(e) => write!(f, "{}", e),
+            InputError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+impl From<io::Error> for InputError {
+    fn from(value: io::Error) -> Self {
+        InputError::Io(value)
+    }
+}
+
+impl From<ParseError> for InputError {
+    fn from(value: ParseError) -> Self {
+        InputError::Parse(value)
+    }
+}
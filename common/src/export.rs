@@ -0,0 +1,103 @@
+//! CSV exports of a day's intermediate state, for inspecting how a solver got its answer (e.g. in
+//! a spreadsheet) rather than reading trace output by hand.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::error::ParseError;
+
+/// Default directory for `aoc export`, with a subdirectory per year.
+pub const EXPORTS_DIR: &str = "exports";
+
+/// Builds a day's exports from its input text.
+pub type Exporter = fn(&str) -> Result<Vec<Export>, ParseError>;
+
+/// A single named CSV table.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Export {
+    /// Used in the file name, e.g. `timeline` is written to `day01_timeline.csv`.
+    pub name: &'static str,
+    pub header: &'static str,
+    pub rows: Vec<String>,
+}
+
+impl Export {
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{}\n", self.header);
+        for row in &self.rows {
+            csv.push_str(row);
+            csv.push('\n');
+        }
+
+        csv
+    }
+}
+
+/// Path a day's export is written to, e.g. `exports/2025/day01_timeline.csv`.
+pub fn export_path(exports_dir: &Path, year: u16, day: u8, name: &str) -> PathBuf {
+    exports_dir
+        .join(year.to_string())
+        .join(format!("day{:02}_{}.csv", day, name))
+}
+
+/// Writes every export for a day, replacing any earlier ones, and returns the paths written.
+pub fn write_exports(
+    exports_dir: &Path,
+    year: u16,
+    day: u8,
+    exports: &[Export],
+) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(exports_dir.join(year.to_string()))?;
+
+    exports
+        .iter()
+        .map(|export| {
+            let path = export_path(exports_dir, year, day, export.name);
+            fs::write(&path, export.to_csv())?;
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn writes_one_csv_per_export() {
+        let exports_dir = env::temp_dir().join(format!("aoc-export-test-{}", std::process::id()));
+        let exports = [
+            Export {
+                name: "squares",
+                header: "n,square",
+                rows: vec!["1,1".to_string(), "2,4".to_string()],
+            },
+            Export {
+                name: "empty",
+                header: "n",
+                rows: Vec::new(),
+            },
+        ];
+
+        let paths = write_exports(&exports_dir, 2025, 3, &exports).unwrap();
+
+        assert_eq!(
+            paths,
+            [
+                exports_dir.join("2025/day03_squares.csv"),
+                exports_dir.join("2025/day03_empty.csv"),
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(&paths[0]).unwrap(),
+            "n,square\n1,1\n2,4\n"
+        );
+        assert_eq!(fs::read_to_string(&paths[1]).unwrap(), "n\n");
+
+        fs::remove_dir_all(exports_dir).unwrap();
+    }
+}
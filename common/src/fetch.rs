@@ -0,0 +1,356 @@
+//! Downloads puzzle inputs into a local cache, so each input is only fetched once.
+//!
+//! Inputs are cached as `inputs/<year>/dayNN.txt` relative to the working directory. Fetching
+//! needs the session token from the Advent of Code website's cookie in the `AOC_SESSION`
+//! environment variable.
+
+use std::{
+    env, fs,
+    io::{self, BufRead, BufReader, ErrorKind, Write},
+    net::TcpStream,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// Default location of the input cache, relative to the working directory.
+pub const INPUTS_DIR: &str = "inputs";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable which overrides the base URL, e.g. to point at a local test server.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+/// Environment variable containing the session token.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+const USER_AGENT: &str = "github.com/Ace4896/advent-of-code";
+
+/// Something that can download a page, given the session token to authenticate with.
+pub trait Fetcher {
+    fn fetch(&self, url: &str, session: &str) -> io::Result<String>;
+}
+
+/// Fetches over HTTPS by shelling out to `curl`.
+///
+/// The session cookie is written to curl's stdin rather than passed as an argument, since
+/// arguments can be read by other users (e.g. with `ps`).
+pub struct CurlFetcher;
+
+impl CurlFetcher {
+    /// Command that fetches the URL, reading the cookie header from stdin.
+    fn command(url: &str) -> Command {
+        let mut command = Command::new("curl");
+        command
+            .args([
+                "--silent",
+                "--show-error",
+                "--fail",
+                "--user-agent",
+                USER_AGENT,
+                "--header",
+                "@-",
+            ])
+            .arg(url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        command
+    }
+}
+
+impl Fetcher for CurlFetcher {
+    fn fetch(&self, url: &str, session: &str) -> io::Result<String> {
+        let mut child = CurlFetcher::command(url).spawn()?;
+
+        // Dropping stdin closes it, so curl knows there are no more headers. If curl exits before
+        // reading it, its own error is more useful than the broken pipe, so that's checked first
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let written = writeln!(stdin, "Cookie: session={}", session);
+        drop(stdin);
+
+        let output = child.wait_with_output()?;
+
+        if !output.status.success() {
+            return Err(io::Error::other(format!(
+                "curl failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        written?;
+
+        String::from_utf8(output.stdout).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+    }
+}
+
+/// Fetches over plain HTTP using the standard library, e.g. from a local test server.
+pub struct HttpFetcher;
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, url: &str, session: &str) -> io::Result<String> {
+        let invalid_url = || {
+            io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("'{}' isn't a plain HTTP URL", url),
+            )
+        };
+
+        let rest = url.strip_prefix("http://").ok_or_else(invalid_url)?;
+        let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+        if host.is_empty() {
+            return Err(invalid_url());
+        }
+
+        let address = if host.contains(':') {
+            host.to_string()
+        } else {
+            format!("{}:80", host)
+        };
+
+        let mut stream = TcpStream::connect(address)?;
+        write!(
+            stream,
+            "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nCookie: session={}\r\nConnection: close\r\n\r\n",
+            if path.is_empty() { "/" } else { path },
+            host,
+            USER_AGENT,
+            session
+        )?;
+
+        read_http_response(BufReader::new(stream))
+    }
+}
+
+/// Picks a fetcher based on the URL's scheme.
+pub fn fetcher_for(base_url: &str) -> Box<dyn Fetcher> {
+    if base_url.starts_with("http://") {
+        Box::new(HttpFetcher)
+    } else {
+        Box::new(CurlFetcher)
+    }
+}
+
+/// Base URL to fetch from, i.e. `AOC_BASE_URL` if it's set.
+pub fn base_url() -> String {
+    env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string())
+}
+
+/// Reads the session token from `AOC_SESSION`.
+pub fn session_token() -> io::Result<String> {
+    env::var(SESSION_VAR)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| {
+            io::Error::new(
+                ErrorKind::NotFound,
+                format!("{} isn't set to a session token", SESSION_VAR),
+            )
+        })
+}
+
+/// Location of a day's input within the cache, e.g. `inputs/2025/day07.txt`.
+pub fn cached_input_path(cache_dir: &Path, year: u16, day: u8) -> PathBuf {
+    cache_dir
+        .join(year.to_string())
+        .join(format!("day{:02}.txt", day))
+}
+
+/// Returns the cached input for a day, downloading it first if it isn't cached yet.
+///
+/// Also returns whether the input was downloaded. Inputs are written to a temporary file first,
+/// so an interrupted download never leaves a partial input in the cache.
+pub fn fetch_input(
+    fetcher: &dyn Fetcher,
+    base_url: &str,
+    session: &str,
+    cache_dir: &Path,
+    year: u16,
+    day: u8,
+) -> io::Result<(PathBuf, bool)> {
+    let path = cached_input_path(cache_dir, year, day);
+    if path.is_file() {
+        return Ok((path, false));
+    }
+
+    let url = format!(
+        "{}/{}/day/{}/input",
+        base_url.trim_end_matches('/'),
+        year,
+        day
+    );
+    let input = fetcher.fetch(&url, session)?;
+
+    fs::create_dir_all(path.parent().unwrap_or(cache_dir))?;
+    let partial_path = path.with_extension("txt.partial");
+    fs::write(&partial_path, input)?;
+    fs::rename(&partial_path, &path)?;
+
+    Ok((path, true))
+}
+
+/// Reads an HTTP/1.x response, returning the body if the status is 200.
+fn read_http_response(mut reader: impl BufRead) -> io::Result<String> {
+    let invalid = |message: &str| io::Error::new(ErrorKind::InvalidData, message.to_string());
+
+    let mut status_line = String::new();
+    reader.read_line(&mut status_line)?;
+
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .ok_or_else(|| invalid("Missing HTTP status"))?;
+    if status != "200" {
+        return Err(io::Error::other(format!(
+            "Server responded with '{}'",
+            status_line.trim()
+        )));
+    }
+
+    let mut chunked = false;
+    let mut content_length: Option<usize> = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("transfer-encoding") {
+                chunked = value.eq_ignore_ascii_case("chunked");
+            } else if name.eq_ignore_ascii_case("content-length") {
+                content_length = Some(
+                    value
+                        .parse()
+                        .map_err(|_| invalid("Invalid Content-Length"))?,
+                );
+            }
+        }
+    }
+
+    let mut body = Vec::new();
+    if chunked {
+        loop {
+            let mut size_line = String::new();
+            reader.read_line(&mut size_line)?;
+
+            let size_str = size_line.trim().split(';').next().unwrap_or_default();
+            let size =
+                usize::from_str_radix(size_str, 16).map_err(|_| invalid("Invalid chunk size"))?;
+            if size == 0 {
+                break;
+            }
+
+            let start = body.len();
+            body.resize(start + size, 0);
+            reader.read_exact(&mut body[start..])?;
+
+            // Skip the CRLF after each chunk
+            reader.read_line(&mut String::new())?;
+        }
+    } else if let Some(length) = content_length {
+        body.resize(length, 0);
+        reader.read_exact(&mut body)?;
+    } else {
+        reader.read_to_end(&mut body)?;
+    }
+
+    String::from_utf8(body).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, net::TcpListener, thread};
+
+    use super::*;
+
+    struct CountingFetcher {
+        calls: Cell<usize>,
+    }
+
+    impl Fetcher for CountingFetcher {
+        fn fetch(&self, url: &str, session: &str) -> io::Result<String> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(format!("{} {}\n", url, session))
+        }
+    }
+
+    #[test]
+    fn only_fetches_uncached_inputs() {
+        let cache_dir = env::temp_dir().join(format!("aoc-fetch-test-{}", std::process::id()));
+        let fetcher = CountingFetcher {
+            calls: Cell::new(0),
+        };
+
+        for expect_fetched in [true, false] {
+            let (path, fetched) =
+                fetch_input(&fetcher, "http://localhost/", "abc", &cache_dir, 2025, 7).unwrap();
+
+            assert_eq!(path, cache_dir.join("2025").join("day07.txt"));
+            assert_eq!(fetched, expect_fetched);
+        }
+
+        assert_eq!(fetcher.calls.get(), 1);
+        assert_eq!(
+            fs::read_to_string(cache_dir.join("2025/day07.txt")).unwrap(),
+            "http://localhost/2025/day/7/input abc\n"
+        );
+
+        fs::remove_dir_all(cache_dir).unwrap();
+    }
+
+    #[test]
+    fn fetches_over_plain_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            while reader.read_line(&mut request).unwrap() > 2 {}
+
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n1 2 \r\n3\r\n3 4\r\n0\r\n\r\n")
+                .unwrap();
+
+            request
+        });
+
+        let url = format!("http://{}/2025/day/1/input", address);
+        assert_eq!(HttpFetcher.fetch(&url, "abc").unwrap(), "1 2 3 4");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2025/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=abc\r\n"));
+    }
+
+    #[test]
+    fn curl_session_is_not_an_argument() {
+        let command = CurlFetcher::command("https://adventofcode.com/2025/day/1/input");
+        let args: Vec<_> = command.get_args().collect();
+
+        assert!(
+            args.iter()
+                .all(|arg| !arg.to_string_lossy().contains("session"))
+        );
+        assert!(args.windows(2).any(|pair| pair == ["--header", "@-"]));
+    }
+
+    #[test]
+    fn reports_http_errors() {
+        let response = "HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n";
+        let error = read_http_response(response.as_bytes()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Server responded with 'HTTP/1.1 400 Bad Request'"
+        );
+
+        let response = "HTTP/1.0 200 OK\r\nContent-Length: 4\r\n\r\nabcdef";
+        assert_eq!(read_http_response(response.as_bytes()).unwrap(), "abcd");
+    }
+}
@@ -0,0 +1,81 @@
+//! Points and coordinate parsing, for puzzles where the input is a list of positions.
+
+use std::str::FromStr;
+
+use crate::error::{ParseError, parse_number};
+
+/// Point in 3D space.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: FromStr> Point3<T> {
+    /// Parses a point written as `x,y,z`.
+    pub fn parse(line_number: usize, line: &str) -> Result<Self, ParseError> {
+        let [x, y, z] = parse_coordinates(line_number, line)?;
+        Ok(Point3 { x, y, z })
+    }
+}
+
+impl Point3<u32> {
+    // Calculating square root is slow and not necessary for direct comparisons, so it's left in squared form
+    pub const fn euclidean_distance_squared(&self, other: &Self) -> u64 {
+        let x_diff = self.x.abs_diff(other.x) as u64;
+        let y_diff = self.y.abs_diff(other.y) as u64;
+        let z_diff = self.z.abs_diff(other.z) as u64;
+
+        (x_diff * x_diff) + (y_diff * y_diff) + (z_diff * z_diff)
+    }
+}
+
+/// Parses exactly `N` comma-separated coordinates, e.g. `162,817,812`.
+pub fn parse_coordinates<T: FromStr, const N: usize>(
+    line_number: usize,
+    line: &str,
+) -> Result<[T; N], ParseError> {
+    let mut coordinates = line.split(',');
+    let mut values = Vec::with_capacity(N);
+
+    for _ in 0..N {
+        // Point at the end of the line if a coordinate is missing
+        let coordinate_str = coordinates.next().unwrap_or(&line[line.len()..]);
+        values.push(parse_number(line_number, line, coordinate_str)?);
+    }
+
+    if let Some(extra) = coordinates.next() {
+        return Err(ParseError::new(
+            line_number,
+            line,
+            extra,
+            "Unexpected coordinate",
+        ));
+    }
+
+    Ok(values
+        .try_into()
+        .unwrap_or_else(|_| unreachable!("Exactly {} coordinates were parsed", N)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_points() {
+        let a = Point3::<u32>::parse(1, "162,817,812").unwrap();
+        let b = Point3::<u32>::parse(2, "57,618,57").unwrap();
+        assert_eq!(
+            a.euclidean_distance_squared(&b),
+            105 * 105 + 199 * 199 + 755 * 755
+        );
+
+        let error = Point3::<u32>::parse(3, "1,2").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 4));
+
+        let error = parse_coordinates::<u64, 2>(4, "1,2,3").unwrap_err();
+        assert_eq!(error.message, "Unexpected coordinate");
+    }
+}
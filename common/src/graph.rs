@@ -0,0 +1,99 @@
+//! Graph helpers, for puzzles where the input describes a directed graph as an adjacency list.
+
+use std::{borrow::Borrow, collections::HashMap, hash::Hash};
+
+use crate::bigint::BigUint;
+
+/// Counts how many distinct paths there are from `start` to `end` in a directed acyclic graph.
+///
+/// Only the part of the graph reachable from `start` is searched, and counts are memoized per
+/// node, so this stays fast even when the number of paths is astronomically large.
+pub fn count_paths<N, Q>(start: &Q, end: &Q, adjacency_list: &HashMap<N, Vec<N>>) -> BigUint
+where
+    N: Borrow<Q> + Eq + Hash,
+    Q: Eq + Hash + ?Sized,
+{
+    // Invert the graph, filtering to nodes that are relevant to search
+    let mut unexplored_nodes: Vec<&Q> = vec![start];
+    let mut inverted_adjacency_list: HashMap<&Q, Vec<&Q>> = HashMap::new();
+
+    while !unexplored_nodes.is_empty() {
+        let nodes = std::mem::take(&mut unexplored_nodes);
+
+        for node in nodes {
+            if let Some(connecting_nodes) = adjacency_list.get(node) {
+                for connecting_node in connecting_nodes.iter().map(|n| n.borrow()) {
+                    if let Some(parent_nodes) = inverted_adjacency_list.get_mut(connecting_node) {
+                        parent_nodes.push(node);
+                    } else {
+                        inverted_adjacency_list.insert(connecting_node, vec![node]);
+
+                        if connecting_node != end {
+                            unexplored_nodes.push(connecting_node);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Then recursively count the total incoming paths for the end node
+    count_paths_inner(
+        start,
+        end,
+        &inverted_adjacency_list,
+        &mut HashMap::with_capacity(inverted_adjacency_list.len()),
+    )
+}
+
+fn count_paths_inner<'a, Q: Eq + Hash + ?Sized>(
+    root_node: &'a Q,
+    node: &'a Q,
+    inverted_adjacency_list: &HashMap<&'a Q, Vec<&'a Q>>,
+    memoized_counts: &mut HashMap<&'a Q, BigUint>,
+) -> BigUint {
+    if node == root_node {
+        // Base case (to allow recursion to complete)
+        BigUint::from(1)
+    } else if let Some(count) = memoized_counts.get(node) {
+        // Already calculated for this node
+        count.clone()
+    } else if let Some(parent_nodes) = inverted_adjacency_list.get(node) {
+        // Sum the total from each parent node and cache value
+        let mut total = BigUint::zero();
+
+        for parent_node in parent_nodes {
+            total += count_paths_inner(
+                root_node,
+                parent_node,
+                inverted_adjacency_list,
+                memoized_counts,
+            );
+        }
+
+        memoized_counts.insert(node, total.clone());
+        total
+    } else {
+        BigUint::zero()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_paths_in_dag() {
+        let adjacency_list = HashMap::from([
+            (1, vec![2, 3]),
+            (2, vec![4]),
+            (3, vec![4, 5]),
+            (4, vec![5]),
+            (6, vec![5]),
+        ]);
+
+        assert_eq!(count_paths(&1, &5, &adjacency_list), 3);
+        assert_eq!(count_paths(&2, &5, &adjacency_list), 1);
+        assert_eq!(count_paths(&5, &1, &adjacency_list), 0);
+    }
+}
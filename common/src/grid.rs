@@ -0,0 +1,520 @@
+//! Flat, row-major 2D grid shared by the grid-based puzzles.
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::error::ParseError;
+
+/// Offsets to the orthogonally adjacent cells.
+const OFFSETS_4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Offsets to the orthogonally and diagonally adjacent cells.
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Cell types that can be parsed from (and rendered back to) a character in a puzzle's grid.
+///
+/// Usually implemented with [`grid_chars!`](crate::grid_chars).
+pub trait FromGridChar: Sized {
+    fn from_grid_char(c: char) -> Option<Self>;
+    fn to_grid_char(&self) -> char;
+}
+
+/// Implements [`FromGridChar`] for a fieldless enum from a table of characters and variants:
+///
+/// ```
+/// # use advent_of_code_common::grid_chars;
+/// enum CellType {
+///     Empty,
+///     PaperRoll,
+/// }
+///
+/// grid_chars! {
+///     CellType {
+///         '.' => Empty,
+///         '@' => PaperRoll,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! grid_chars {
+    ($cell_type:ty { $($c:literal => $variant:ident),+ $(,)? }) => {
+        impl $crate::grid::FromGridChar for $cell_type {
+            fn from_grid_char(c: char) -> Option<Self> {
+                match c {
+                    $($c => Some(Self::$variant),)+
+                    _ => None,
+                }
+            }
+
+            fn to_grid_char(&self) -> char {
+                match self {
+                    $(Self::$variant => $c,)+
+                }
+            }
+        }
+    };
+}
+
+/// `#` for filled cells and `.` for empty cells, as used by most puzzles.
+impl FromGridChar for bool {
+    fn from_grid_char(c: char) -> Option<Self> {
+        match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+
+    fn to_grid_char(&self) -> char {
+        if *self { '#' } else { '.' }
+    }
+}
+
+/// 2D grid stored as a single row-major `Vec`, indexed by `(row, col)`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from row-major data.
+    ///
+    /// Panics if the data length doesn't match the dimensions.
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<T>) -> Self {
+        assert_eq!(data.len(), rows * cols, "Data doesn't match grid size");
+
+        Grid { data, rows, cols }
+    }
+
+    /// Creates a grid by calling a function with the position of each cell.
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let data = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .map(|(row, col)| f(row, col))
+            .collect();
+
+        Grid { data, rows, cols }
+    }
+
+    /// Creates a grid from a list of rows, or returns `None` if the rows have different lengths.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let row_count = rows.len();
+        let col_count = rows.first().map_or(0, |row| row.len());
+
+        if rows.iter().any(|row| row.len() != col_count) {
+            return None;
+        }
+
+        Some(Grid {
+            data: rows.into_iter().flatten().collect(),
+            rows: row_count,
+            cols: col_count,
+        })
+    }
+
+    pub const fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub const fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    const fn calculate_idx(&self, row: usize, col: usize) -> usize {
+        (self.cols * row) + col
+    }
+
+    pub const fn in_bounds(&self, row: usize, col: usize) -> bool {
+        row < self.rows && col < self.cols
+    }
+
+    /// Bounds-checked access to a cell.
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.in_bounds(row, col)
+            .then(|| &self.data[self.calculate_idx(row, col)])
+    }
+
+    /// Bounds-checked mutable access to a cell.
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if self.in_bounds(row, col) {
+            let idx = self.calculate_idx(row, col);
+            Some(&mut self.data[idx])
+        } else {
+            None
+        }
+    }
+
+    /// Access to a cell without bounds checks.
+    ///
+    /// # Safety
+    ///
+    /// `row` and `col` must be within the grid.
+    pub unsafe fn get_unchecked(&self, row: usize, col: usize) -> &T {
+        debug_assert!(self.in_bounds(row, col));
+        unsafe { self.data.get_unchecked(self.calculate_idx(row, col)) }
+    }
+
+    /// Mutable access to a cell without bounds checks.
+    ///
+    /// # Safety
+    ///
+    /// `row` and `col` must be within the grid.
+    pub unsafe fn get_unchecked_mut(&mut self, row: usize, col: usize) -> &mut T {
+        debug_assert!(self.in_bounds(row, col));
+        let idx = self.calculate_idx(row, col);
+        unsafe { self.data.get_unchecked_mut(idx) }
+    }
+
+    /// Positions of the (up to 4) orthogonally adjacent cells.
+    pub fn neighbours4(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        offset_positions(self.rows, self.cols, row, col, &OFFSETS_4)
+    }
+
+    /// Positions of the (up to 8) orthogonally and diagonally adjacent cells.
+    pub fn neighbours8(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        offset_positions(self.rows, self.cols, row, col, &OFFSETS_8)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        let start = self.calculate_idx(row, 0);
+        &self.data[start..(start + self.cols)]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        let start = self.calculate_idx(row, 0);
+        &mut self.data[start..(start + self.cols)]
+    }
+
+    /// Iterates over the rows, top to bottom.
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks_exact` doesn't accept a chunk size of 0
+        self.data.chunks_exact(self.cols.max(1)).take(self.rows)
+    }
+
+    /// Iterates over the cells in a column, top to bottom.
+    pub fn iter_col(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.cols, "Column {} out of bounds", col);
+        self.data.iter().skip(col).step_by(self.cols)
+    }
+
+    /// Iterates over all cells in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.data.iter()
+    }
+
+    /// Iterates over all cells in row-major order, along with their positions.
+    pub fn iter_indexed(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let cols = self.cols;
+
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(idx, cell)| ((idx / cols, idx % cols), cell))
+    }
+
+    /// Creates a new grid of the same size by applying a function to each cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to the same value.
+    pub fn new(rows: usize, cols: usize, value: T) -> Self {
+        Grid {
+            data: vec![value; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self {
+        Grid::from_fn(self.cols, self.rows, |row, col| self[(col, row)].clone())
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        Grid::from_fn(self.cols, self.rows, |row, col| {
+            self[(self.rows - 1 - col, row)].clone()
+        })
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Self {
+        Grid::from_fn(self.cols, self.rows, |row, col| {
+            self[(col, self.cols - 1 - row)].clone()
+        })
+    }
+
+    /// Mirrors the grid left-to-right.
+    pub fn flip_horizontal(&self) -> Self {
+        Grid::from_fn(self.rows, self.cols, |row, col| {
+            self[(row, self.cols - 1 - col)].clone()
+        })
+    }
+
+    /// Mirrors the grid top-to-bottom.
+    pub fn flip_vertical(&self) -> Self {
+        Grid::from_fn(self.rows, self.cols, |row, col| {
+            self[(self.rows - 1 - row, col)].clone()
+        })
+    }
+}
+
+impl<T: FromGridChar> Grid<T> {
+    /// Parses a character map, skipping any blank lines.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::parse_lines(input.lines().enumerate().map(|(idx, l)| (idx + 1, l)))
+    }
+
+    /// Parses a character map from `(line_number, line)` pairs, skipping any blank lines.
+    ///
+    /// Every row must be the same length.
+    pub fn parse_lines<'a>(
+        lines: impl IntoIterator<Item = (usize, &'a str)>,
+    ) -> Result<Self, ParseError> {
+        let mut data = Vec::new();
+        let mut rows = 0;
+        let mut cols = 0;
+
+        for (line_number, line) in lines {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let row_start = data.len();
+            for (char_idx, c) in line.char_indices() {
+                let cell = T::from_grid_char(c).ok_or_else(|| {
+                    let token = &line[char_idx..(char_idx + c.len_utf8())];
+                    ParseError::new(line_number, line, token, "Invalid grid cell")
+                })?;
+
+                data.push(cell);
+            }
+
+            let row_len = data.len() - row_start;
+            if rows == 0 {
+                cols = row_len;
+            } else if row_len != cols {
+                return Err(ParseError::new(
+                    line_number,
+                    line,
+                    line,
+                    "Row length doesn't match the first row",
+                ));
+            }
+
+            rows += 1;
+        }
+
+        Ok(Grid { data, rows, cols })
+    }
+}
+
+/// Renders the grid in the puzzle's notation, one line per row.
+impl<T: FromGridChar> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (row_idx, row) in self.iter_rows().enumerate() {
+            if row_idx > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell.to_grid_char())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    /// Panics if the position is out of bounds.
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        self.get(row, col).unwrap_or_else(|| {
+            panic!(
+                "Position ({}, {}) out of bounds for {}x{} grid",
+                row, col, self.rows, self.cols
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    /// Panics if the position is out of bounds.
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        let (rows, cols) = (self.rows, self.cols);
+
+        self.get_mut(row, col).unwrap_or_else(|| {
+            panic!(
+                "Position ({}, {}) out of bounds for {}x{} grid",
+                row, col, rows, cols
+            )
+        })
+    }
+}
+
+fn offset_positions(
+    rows: usize,
+    cols: usize,
+    row: usize,
+    col: usize,
+    offsets: &'static [(isize, isize)],
+) -> impl Iterator<Item = (usize, usize)> {
+    offsets.iter().filter_map(move |&(row_offset, col_offset)| {
+        let neighbour_row = row.checked_add_signed(row_offset)?;
+        let neighbour_col = col.checked_add_signed(col_offset)?;
+
+        (neighbour_row < rows && neighbour_col < cols).then_some((neighbour_row, neighbour_col))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2x3 grid:
+    ///
+    /// ```text
+    /// 0 1 2
+    /// 3 4 5
+    /// ```
+    fn numbered_grid() -> Grid<u8> {
+        Grid::from_vec(2, 3, vec![0, 1, 2, 3, 4, 5])
+    }
+
+    fn cells(grid: &Grid<u8>) -> Vec<Vec<u8>> {
+        grid.iter_rows().map(|row| row.to_vec()).collect()
+    }
+
+    #[test]
+    fn indexes_non_square_grids() {
+        let grid = numbered_grid();
+
+        assert_eq!(grid[(0, 2)], 2);
+        assert_eq!(grid[(1, 0)], 3);
+        assert_eq!(grid[(1, 2)], 5);
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(unsafe { *grid.get_unchecked(1, 1) }, 4);
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = numbered_grid();
+
+        assert_eq!(cells(&grid), vec![vec![0, 1, 2], vec![3, 4, 5]]);
+        assert_eq!(grid.iter_col(1).copied().collect::<Vec<_>>(), vec![1, 4]);
+        assert_eq!(grid.iter_indexed().nth(4), Some(((1, 1), &4)));
+    }
+
+    #[test]
+    fn finds_neighbours_within_bounds() {
+        let grid = numbered_grid();
+
+        assert_eq!(
+            grid.neighbours4(0, 0).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbours8(0, 1).count(), 5);
+        assert_eq!(grid.neighbours8(1, 2).count(), 3);
+    }
+
+    #[test]
+    fn rotates_and_flips() {
+        let grid = numbered_grid();
+
+        assert_eq!(
+            cells(&grid.transpose()),
+            vec![vec![0, 3], vec![1, 4], vec![2, 5]]
+        );
+        assert_eq!(
+            cells(&grid.rotate_clockwise()),
+            vec![vec![3, 0], vec![4, 1], vec![5, 2]]
+        );
+        assert_eq!(
+            cells(&grid.rotate_counter_clockwise()),
+            vec![vec![2, 5], vec![1, 4], vec![0, 3]]
+        );
+        assert_eq!(
+            cells(&grid.flip_horizontal()),
+            vec![vec![2, 1, 0], vec![5, 4, 3]]
+        );
+        assert_eq!(
+            cells(&grid.flip_vertical()),
+            vec![vec![3, 4, 5], vec![0, 1, 2]]
+        );
+        assert_eq!(grid.rotate_clockwise().rotate_counter_clockwise(), grid);
+    }
+
+    #[test]
+    fn parses_character_maps() {
+        let grid = Grid::<bool>::parse("#..\n\n.##\n").unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert!(grid[(0, 0)] && grid[(1, 2)] && !grid[(1, 0)]);
+
+        let error = Grid::<bool>::parse("#..\n.x.\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+
+        let error = Grid::<bool>::parse("#..\n.#\n").unwrap_err();
+        assert_eq!(error.message, "Row length doesn't match the first row");
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    enum TestCell {
+        Open,
+        Wall,
+        Start,
+    }
+
+    grid_chars! {
+        TestCell {
+            '.' => Open,
+            '#' => Wall,
+            'S' => Start,
+        }
+    }
+
+    #[test]
+    fn renders_parsed_grids() {
+        let input = "S.#\n.##";
+        let mut grid = Grid::<TestCell>::parse(input).unwrap();
+        assert_eq!(grid[(0, 0)], TestCell::Start);
+        assert_eq!(grid.to_string(), input);
+
+        grid[(1, 0)] = TestCell::Wall;
+        assert_eq!(grid.to_string(), "S.#\n###");
+        assert_eq!(grid.rotate_clockwise().to_string(), "#S\n#.\n##");
+    }
+}
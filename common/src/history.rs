@@ -0,0 +1,386 @@
+//! History of benchmark timings, keyed by git commit, for catching performance regressions.
+//!
+//! Records are appended to a CSV file with one row per day and stage:
+//!
+//! ```text
+//! commit,timestamp,year,day,stage,iterations,min_ns,median_ns,p95_ns,peak_alloc_bytes
+//! 1a2b3c4,1765432100,2025,11,part2,100,2950,3200,4100,2048
+//! ```
+
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    process::Command,
+    sync::OnceLock,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    bench::{BenchReport, Stats, format_duration},
+    error::{InputError, ParseError, parse_number},
+};
+
+/// Default location of the timing history, relative to the working directory.
+pub const HISTORY_PATH: &str = "bench_history.csv";
+
+/// Default percentage increase in median time that counts as a regression.
+pub const DEFAULT_THRESHOLD_PERCENT: f64 = 10.0;
+
+const CSV_HEADER: &str =
+    "commit,timestamp,year,day,stage,iterations,min_ns,median_ns,p95_ns,peak_alloc_bytes";
+
+/// Benchmark timings for one stage of a day at a particular commit.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HistoryRecord {
+    pub commit: String,
+    /// Seconds since the Unix epoch when the benchmark was run.
+    pub timestamp: u64,
+    pub year: u16,
+    pub day: u8,
+    /// Stage name, i.e. `parse`, `part1` or `part2`.
+    pub stage: String,
+    pub iterations: usize,
+    pub stats: Stats,
+    pub peak_alloc: Option<usize>,
+}
+
+impl HistoryRecord {
+    /// Creates a record for each stage of a benchmark report for a day in `year`.
+    pub fn from_report(commit: &str, timestamp: u64, year: u16, report: &BenchReport) -> Vec<Self> {
+        let stages =
+            std::iter::once(("parse".to_string(), &report.parse)).chain(
+                report.parts.iter().enumerate().filter_map(|(idx, stage)| {
+                    stage.as_ref().map(|s| (format!("part{}", idx + 1), s))
+                }),
+            );
+
+        stages
+            .map(|(stage, stage_report)| HistoryRecord {
+                commit: commit.to_string(),
+                timestamp,
+                year,
+                day: report.day,
+                stage,
+                iterations: report.iterations,
+                stats: stage_report.stats,
+                peak_alloc: stage_report.peak_alloc,
+            })
+            .collect()
+    }
+
+    fn to_csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{}",
+            self.commit,
+            self.timestamp,
+            self.year,
+            self.day,
+            self.stage,
+            self.iterations,
+            self.stats.min.as_nanos(),
+            self.stats.median.as_nanos(),
+            self.stats.p95.as_nanos(),
+            self.peak_alloc.map(|p| p.to_string()).unwrap_or_default(),
+        )
+    }
+
+    fn parse_csv_row(line_number: usize, line: &str) -> Result<Self, ParseError> {
+        let fields: Vec<&str> = line.split(',').collect();
+        if fields.len() != 10 {
+            let message = match fields.len() {
+                9 => {
+                    "Expected 10 comma-separated fields (older rows need a year after the timestamp)"
+                }
+                _ => "Expected 10 comma-separated fields",
+            };
+
+            return Err(ParseError::new(line_number, line, line, message));
+        }
+
+        let nanos = |field: &str| -> Result<Duration, ParseError> {
+            Ok(Duration::from_nanos(parse_number(
+                line_number,
+                line,
+                field,
+            )?))
+        };
+
+        Ok(HistoryRecord {
+            commit: fields[0].to_string(),
+            timestamp: parse_number(line_number, line, fields[1])?,
+            year: parse_number(line_number, line, fields[2])?,
+            day: parse_number(line_number, line, fields[3])?,
+            stage: fields[4].to_string(),
+            iterations: parse_number(line_number, line, fields[5])?,
+            stats: Stats {
+                min: nanos(fields[6])?,
+                median: nanos(fields[7])?,
+                p95: nanos(fields[8])?,
+            },
+            peak_alloc: match fields[9] {
+                "" => None,
+                field => Some(parse_number(line_number, line, field)?),
+            },
+        })
+    }
+}
+
+/// Loads the timing history, or returns an empty history if the file doesn't exist yet.
+pub fn load(path: &Path) -> Result<Vec<HistoryRecord>, InputError> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut records = Vec::new();
+    for (line_idx, line) in contents.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() || line == CSV_HEADER {
+            continue;
+        }
+
+        records.push(HistoryRecord::parse_csv_row(line_idx + 1, line)?);
+    }
+
+    Ok(records)
+}
+
+/// Appends records to the timing history, creating the file if needed.
+pub fn append(path: &Path, records: &[HistoryRecord]) -> io::Result<()> {
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    if is_new {
+        writeln!(file, "{}", CSV_HEADER)?;
+    }
+
+    for record in records {
+        writeln!(file, "{}", record.to_csv_row())?;
+    }
+
+    Ok(())
+}
+
+/// Short hash of the current git commit, with a `-dirty` suffix if there are uncommitted
+/// changes. Falls back to `unknown` outside of a git repository.
+pub fn current_commit() -> &'static str {
+    static COMMIT: OnceLock<String> = OnceLock::new();
+
+    COMMIT.get_or_init(find_current_commit)
+}
+
+fn find_current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit) if git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty()) => {
+            format!("{}-dirty", commit)
+        }
+        Some(commit) => commit,
+        None => "unknown".to_string(),
+    }
+}
+
+/// Current time in seconds since the Unix epoch.
+pub fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Commits in the history, in the order they were first recorded.
+pub fn commits(records: &[HistoryRecord]) -> Vec<&str> {
+    let mut commits: Vec<&str> = Vec::new();
+    for record in records {
+        if !commits.contains(&record.commit.as_str()) {
+            commits.push(&record.commit);
+        }
+    }
+
+    commits
+}
+
+/// Change in median time for one stage of a day between two commits.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Comparison {
+    pub year: u16,
+    pub day: u8,
+    pub stage: String,
+    pub base: Duration,
+    pub new: Duration,
+}
+
+impl Comparison {
+    /// Percentage change from the base median to the new median (positive means slower).
+    pub fn change_percent(&self) -> f64 {
+        let base = self.base.as_secs_f64();
+        if base == 0.0 {
+            return 0.0;
+        }
+
+        (self.new.as_secs_f64() - base) / base * 100.0
+    }
+
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.change_percent() > threshold_percent
+    }
+}
+
+/// Compares the median timings of every year, day and stage measured at both commits.
+///
+/// If a commit was measured more than once, its latest measurement is used.
+pub fn compare(records: &[HistoryRecord], base_commit: &str, new_commit: &str) -> Vec<Comparison> {
+    let latest_medians = |commit: &str| {
+        records
+            .iter()
+            .filter(|r| r.commit == commit)
+            .map(|r| ((r.year, r.day, r.stage.clone()), r.stats.median))
+            .collect::<HashMap<_, _>>()
+    };
+
+    let base_medians = latest_medians(base_commit);
+    let new_medians = latest_medians(new_commit);
+
+    let mut comparisons: Vec<Comparison> = new_medians
+        .into_iter()
+        .filter_map(|((year, day, stage), new)| {
+            base_medians
+                .get(&(year, day, stage.clone()))
+                .map(|&base| Comparison {
+                    year,
+                    day,
+                    stage,
+                    base,
+                    new,
+                })
+        })
+        .collect();
+
+    comparisons.sort_by(|a, b| (a.year, a.day, &a.stage).cmp(&(b.year, b.day, &b.stage)));
+    comparisons
+}
+
+/// Formats a comparison as a line of the `aoc compare` report.
+pub fn format_comparison(comparison: &Comparison, threshold_percent: f64) -> String {
+    format!(
+        "{} Day {:>2} {:<6} {:>10} -> {:>10} ({:+.1}%){}",
+        comparison.year,
+        comparison.day,
+        comparison.stage,
+        format_duration(comparison.base),
+        format_duration(comparison.new),
+        comparison.change_percent(),
+        if comparison.is_regression(threshold_percent) {
+            "  REGRESSION"
+        } else {
+            ""
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(commit: &str, day: u8, stage: &str, median_us: u64) -> HistoryRecord {
+        let median = Duration::from_micros(median_us);
+
+        HistoryRecord {
+            commit: commit.to_string(),
+            timestamp: 1765432100,
+            year: 2025,
+            day,
+            stage: stage.to_string(),
+            iterations: 100,
+            stats: Stats {
+                min: median / 2,
+                median,
+                p95: median * 2,
+            },
+            peak_alloc: Some(2048),
+        }
+    }
+
+    #[test]
+    fn round_trips_through_csv() {
+        let mut original = record("1a2b3c4", 11, "part2", 3);
+        let row = original.to_csv_row();
+        assert_eq!(HistoryRecord::parse_csv_row(1, &row).unwrap(), original);
+
+        original.peak_alloc = None;
+        let row = original.to_csv_row();
+        assert_eq!(HistoryRecord::parse_csv_row(1, &row).unwrap(), original);
+    }
+
+    #[test]
+    fn flags_regressions_above_threshold() {
+        let records = vec![
+            record("old", 4, "part2", 100),
+            record("old", 11, "part2", 100),
+            record("new", 4, "part2", 105),
+            record("new", 11, "part2", 150),
+            record("new", 12, "part1", 1),
+        ];
+
+        let comparisons = compare(&records, "old", "new");
+        assert_eq!(comparisons.len(), 2);
+        assert!(!comparisons[0].is_regression(10.0));
+        assert!(comparisons[1].is_regression(10.0));
+        assert!(!comparisons[1].is_regression(60.0));
+
+        assert_eq!(commits(&records), vec!["old", "new"]);
+    }
+
+    #[test]
+    fn uses_latest_measurement_per_commit() {
+        let records = vec![
+            record("old", 4, "parse", 100),
+            record("new", 4, "parse", 200),
+            record("new", 4, "parse", 100),
+        ];
+
+        let comparisons = compare(&records, "old", "new");
+        assert_eq!(comparisons[0].new, Duration::from_micros(100));
+    }
+
+    #[test]
+    fn compares_the_same_day_of_each_year_separately() {
+        let in_year = |year, commit, median_us| HistoryRecord {
+            year,
+            ..record(commit, 4, "part1", median_us)
+        };
+        let records = vec![
+            in_year(2025, "old", 100),
+            in_year(2026, "old", 1000),
+            in_year(2025, "new", 100),
+            in_year(2026, "new", 2000),
+        ];
+
+        let comparisons = compare(&records, "old", "new");
+        let changes: Vec<(u16, f64)> = comparisons
+            .iter()
+            .map(|c| (c.year, c.change_percent()))
+            .collect();
+        assert_eq!(changes, [(2025, 0.0), (2026, 100.0)]);
+
+        assert!(format_comparison(&comparisons[1], 10.0).starts_with("2026 Day  4 part1"));
+    }
+
+    #[test]
+    fn rejects_rows_without_a_year() {
+        let row = "1a2b3c4,1765432100,11,part2,100,2950,3200,4100,2048";
+        let error = HistoryRecord::parse_csv_row(2, row).unwrap_err();
+        assert!(error.message.contains("need a year"));
+    }
+}
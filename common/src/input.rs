@@ -0,0 +1,195 @@
+//! Helpers for splitting up puzzle input text, keeping track of line numbers for errors.
+//!
+//! Line endings (`\n` or `\r\n`) and trailing whitespace are handled the same way everywhere, so
+//! parsers don't need to worry about how the input file was saved.
+
+use std::str::FromStr;
+
+use crate::{
+    error::{ParseError, parse_number},
+    grid::{FromGridChar, Grid},
+};
+
+/// Puzzle input text (or a section of it).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Input<'a> {
+    text: &'a str,
+    /// Line number of the first line of the text within the whole input.
+    first_line: usize,
+}
+
+/// A token within a line of the input, used for parsing values and reporting errors.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Token<'a> {
+    pub line_number: usize,
+    pub line: &'a str,
+    pub text: &'a str,
+}
+
+impl<'a> Input<'a> {
+    pub const fn new(text: &'a str) -> Self {
+        Input {
+            text,
+            first_line: 1,
+        }
+    }
+
+    /// The raw text, including line endings and trailing whitespace.
+    pub const fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Line number of the first line within the whole input.
+    pub const fn first_line(&self) -> usize {
+        self.first_line
+    }
+
+    /// Iterates over `(line_number, line)` pairs, with trailing whitespace (including `\r`)
+    /// removed. Blank lines are included.
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &'a str)> + use<'a> {
+        let first_line = self.first_line;
+
+        self.text
+            .lines()
+            .enumerate()
+            .map(move |(idx, line)| (first_line + idx, line.trim_end()))
+    }
+
+    /// Iterates over the non-blank lines.
+    pub fn non_empty_lines(&self) -> impl Iterator<Item = (usize, &'a str)> + use<'a> {
+        self.lines().filter(|(_, line)| !line.trim().is_empty())
+    }
+
+    /// Splits the input into sections separated by one or more blank lines.
+    pub fn sections(&self) -> impl Iterator<Item = Input<'a>> + use<'a> {
+        let mut sections = Vec::new();
+        let mut section_start: Option<(usize, usize)> = None;
+        let mut offset = 0;
+
+        for (idx, raw_line) in self.text.split_inclusive('\n').enumerate() {
+            let line_number = self.first_line + idx;
+
+            match (raw_line.trim().is_empty(), section_start) {
+                (false, None) => section_start = Some((offset, line_number)),
+                (true, Some((start, first_line))) => {
+                    sections.push(Input {
+                        text: &self.text[start..offset],
+                        first_line,
+                    });
+                    section_start = None;
+                }
+                _ => {}
+            }
+
+            offset += raw_line.len();
+        }
+
+        if let Some((start, first_line)) = section_start {
+            sections.push(Input {
+                text: &self.text[start..],
+                first_line,
+            });
+        }
+
+        sections.into_iter()
+    }
+
+    /// Parses the non-blank lines as a character map.
+    pub fn grid<T: FromGridChar>(&self) -> Result<Grid<T>, ParseError> {
+        Grid::parse_lines(self.lines())
+    }
+
+    /// Parses every whitespace-separated token as a number.
+    pub fn numbers<T: FromStr>(&self) -> Result<Vec<T>, ParseError> {
+        self.lines()
+            .flat_map(|(line_number, line)| {
+                line.split_whitespace()
+                    .map(move |token| parse_number(line_number, line, token))
+            })
+            .collect()
+    }
+
+    /// Splits every line on commas, returning the trimmed fields. Empty fields (e.g. from a
+    /// trailing comma) are skipped.
+    pub fn split_csv(&self) -> impl Iterator<Item = Token<'a>> + use<'a> {
+        self.lines().flat_map(|(line_number, line)| {
+            line.split(',')
+                .map(str::trim)
+                .filter(|field| !field.is_empty())
+                .map(move |text| Token {
+                    line_number,
+                    line,
+                    text,
+                })
+        })
+    }
+}
+
+impl<'a> From<&'a str> for Input<'a> {
+    fn from(text: &'a str) -> Self {
+        Input::new(text)
+    }
+}
+
+impl<'a> Token<'a> {
+    /// Parses the token as a number.
+    pub fn parse<T: FromStr>(&self) -> Result<T, ParseError> {
+        parse_number(self.line_number, self.line, self.text)
+    }
+
+    /// Creates an error pointing at the token.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.line_number, self.line, self.text, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalises_line_endings_and_trailing_whitespace() {
+        let input = Input::new("ab  \r\ncd\r\n\r\nef\t\n");
+
+        assert_eq!(
+            input.lines().collect::<Vec<_>>(),
+            vec![(1, "ab"), (2, "cd"), (3, ""), (4, "ef")]
+        );
+        assert_eq!(input.non_empty_lines().count(), 3);
+    }
+
+    #[test]
+    fn splits_sections_with_line_numbers() {
+        let input = Input::new("\n1-2\r\n3-4\r\n  \r\n\r\n5\n6\n\n");
+        let sections: Vec<_> = input.sections().collect();
+
+        assert_eq!(sections.len(), 2);
+        assert_eq!(
+            sections[0].lines().collect::<Vec<_>>(),
+            vec![(2, "1-2"), (3, "3-4")]
+        );
+        assert_eq!(sections[1].first_line(), 6);
+        assert_eq!(sections[1].numbers::<u32>().unwrap(), vec![5, 6]);
+    }
+
+    #[test]
+    fn parses_numbers_and_csv() {
+        let error = Input::new("1 2\n3 x\n").numbers::<u8>().unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.token.as_str()),
+            (2, 3, "x")
+        );
+
+        let fields: Vec<_> = Input::new("a, b,\nc\r\n")
+            .split_csv()
+            .map(|t| (t.line_number, t.text))
+            .collect();
+        assert_eq!(fields, vec![(1, "a"), (1, "b"), (2, "c")]);
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid: Grid<bool> = Input::new("#.\r\n.#\r\n").grid().unwrap();
+        assert_eq!(grid.to_string(), "#.\n.#");
+    }
+}
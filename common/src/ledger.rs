@@ -0,0 +1,307 @@
+//! Ledger of known answers, so solutions can be checked against real inputs after refactoring.
+//!
+//! Answers are stored in a small TOML file, with one table per day and input hash:
+//!
+//! ```toml
+//! [day1.e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855]
+//! part1 = "1150"
+//! part2 = "6738"
+//! ```
+
+use std::{collections::BTreeMap, fmt, fs, io, path::Path};
+
+use crate::error::{InputError, ParseError};
+
+/// Default location of the answer ledger, relative to the working directory.
+pub const ANSWERS_PATH: &str = "answers.toml";
+
+/// Known answers, keyed by day, input hash and part.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct AnswerLedger {
+    entries: BTreeMap<(u8, String), BTreeMap<usize, String>>,
+}
+
+impl AnswerLedger {
+    /// Loads the ledger from a file, or returns an empty ledger if the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self, InputError> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(Self::parse(&contents)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes the ledger to a file.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut ledger = Self::default();
+        let mut current_key: Option<(u8, String)> = None;
+
+        for (line_idx, line) in input.lines().enumerate() {
+            let line_number = line_idx + 1;
+            let trimmed = line.trim();
+
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            if let Some(header) = trimmed.strip_prefix('[') {
+                let header = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| ParseError::new(line_number, line, trimmed, "Expected ']'"))?;
+
+                current_key = Some(parse_table_header(line_number, line, header)?);
+                continue;
+            }
+
+            let key = current_key.clone().ok_or_else(|| {
+                ParseError::new(line_number, line, trimmed, "Expected a [dayN.hash] table")
+            })?;
+
+            let (part, answer) = parse_answer(line_number, line, trimmed)?;
+            ledger.entries.entry(key).or_default().insert(part, answer);
+        }
+
+        Ok(ledger)
+    }
+
+    /// Looks up the known answer for a part of a day.
+    pub fn get(&self, day: u8, part: usize, input_hash: &str) -> Option<&str> {
+        self.entries
+            .get(&(day, input_hash.to_string()))
+            .and_then(|parts| parts.get(&part))
+            .map(|answer| answer.as_str())
+    }
+
+    /// Records the answer for a part of a day, replacing any existing answer.
+    pub fn insert(&mut self, day: u8, part: usize, input_hash: &str, answer: &str) {
+        self.entries
+            .entry((day, input_hash.to_string()))
+            .or_default()
+            .insert(part, answer.to_string());
+    }
+
+    /// Compares an answer against the ledger.
+    pub fn verify(&self, day: u8, part: usize, input_hash: &str, answer: &str) -> Verdict {
+        match self.get(day, part, input_hash) {
+            None => Verdict::New,
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for AnswerLedger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "# Known answers, keyed by day and the SHA-256 hash of the puzzle input"
+        )?;
+
+        for ((day, input_hash), parts) in &self.entries {
+            writeln!(f)?;
+            writeln!(f, "[day{}.{}]", day, input_hash)?;
+
+            for (part, answer) in parts {
+                writeln!(f, "part{} = \"{}\"", part, escape(answer))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Outcome of checking an answer against the ledger.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    New,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail { expected } => write!(f, "FAIL, expected {}", expected),
+            Verdict::New => write!(f, "NEW"),
+        }
+    }
+}
+
+/// How the runner should use the answer ledger.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LedgerMode {
+    /// Check answers against the ledger without modifying it.
+    Verify,
+    /// Check answers against the ledger, and record any new answers.
+    ///
+    /// Existing answers are never overwritten; if a known answer is wrong, edit the ledger by
+    /// hand.
+    Record,
+}
+
+/// An answer ledger opened by the runner, which keeps track of any failed checks.
+pub struct LedgerSession {
+    mode: LedgerMode,
+    ledger: AnswerLedger,
+    failed: bool,
+}
+
+impl LedgerSession {
+    pub fn open(path: &Path, mode: LedgerMode) -> Result<Self, InputError> {
+        Ok(LedgerSession {
+            mode,
+            ledger: AnswerLedger::load(path)?,
+            failed: false,
+        })
+    }
+
+    /// Checks an answer against the ledger, recording it if it's new and the session is in
+    /// record mode.
+    pub fn check(&mut self, day: u8, part: usize, input_hash: &str, answer: &str) -> Verdict {
+        let verdict = self.ledger.verify(day, part, input_hash, answer);
+
+        match verdict {
+            Verdict::Fail { .. } => self.failed = true,
+            Verdict::New if self.mode == LedgerMode::Record => {
+                self.ledger.insert(day, part, input_hash, answer)
+            }
+            _ => {}
+        }
+
+        verdict
+    }
+
+    /// Whether any answer didn't match the ledger.
+    pub fn has_failures(&self) -> bool {
+        self.failed
+    }
+
+    /// Saves any newly recorded answers.
+    pub fn finish(&self, path: &Path) -> io::Result<()> {
+        match self.mode {
+            LedgerMode::Record => self.ledger.save(path),
+            LedgerMode::Verify => Ok(()),
+        }
+    }
+}
+
+fn parse_table_header(
+    line_number: usize,
+    line: &str,
+    header: &str,
+) -> Result<(u8, String), ParseError> {
+    let (day_str, input_hash) = header
+        .split_once('.')
+        .ok_or_else(|| ParseError::new(line_number, line, header, "Expected [dayN.hash]"))?;
+
+    let day = day_str
+        .strip_prefix("day")
+        .and_then(|d| d.parse::<u8>().ok())
+        .ok_or_else(|| ParseError::new(line_number, line, day_str, "Invalid day"))?;
+
+    if input_hash.is_empty() || !input_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ParseError::new(
+            line_number,
+            line,
+            input_hash,
+            "Invalid input hash",
+        ));
+    }
+
+    Ok((day, input_hash.to_string()))
+}
+
+fn parse_answer(
+    line_number: usize,
+    line: &str,
+    entry: &str,
+) -> Result<(usize, String), ParseError> {
+    let (key, value) = entry
+        .split_once('=')
+        .ok_or_else(|| ParseError::new(line_number, line, entry, "Expected 'partN = \"...\"'"))?;
+
+    let key = key.trim();
+    let part = key
+        .strip_prefix("part")
+        .and_then(|p| p.parse::<usize>().ok())
+        .ok_or_else(|| ParseError::new(line_number, line, key, "Invalid part"))?;
+
+    let value = value.trim();
+    let answer = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .filter(|v| v.len() + 2 == value.len())
+        .ok_or_else(|| ParseError::new(line_number, line, value, "Expected a quoted answer"))?;
+
+    Ok((part, unescape(answer)))
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            unescaped.extend(chars.next());
+        } else {
+            unescaped.push(c);
+        }
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    #[test]
+    fn round_trips_through_toml() {
+        let mut ledger = AnswerLedger::default();
+        ledger.insert(1, 1, HASH, "1150");
+        ledger.insert(1, 2, HASH, "6738");
+        ledger.insert(12, 2, HASH, "N/A \"quoted\"");
+
+        let parsed = AnswerLedger::parse(&ledger.to_string()).unwrap();
+        assert_eq!(parsed, ledger);
+        assert_eq!(parsed.get(12, 2, HASH), Some("N/A \"quoted\""));
+    }
+
+    #[test]
+    fn verifies_answers() {
+        let mut ledger = AnswerLedger::default();
+        ledger.insert(3, 1, HASH, "357");
+
+        assert_eq!(ledger.verify(3, 1, HASH, "357"), Verdict::Pass);
+        assert_eq!(
+            ledger.verify(3, 1, HASH, "358"),
+            Verdict::Fail {
+                expected: "357".to_string()
+            }
+        );
+        assert_eq!(ledger.verify(3, 2, HASH, "1"), Verdict::New);
+        assert_eq!(ledger.verify(3, 1, "abc123", "357"), Verdict::New);
+    }
+
+    #[test]
+    fn reports_malformed_entries() {
+        let error = AnswerLedger::parse("[day1.abc]\npart1 = 1150\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 9));
+
+        let error = AnswerLedger::parse("part1 = \"1\"\n").unwrap_err();
+        assert_eq!(error.line, 1);
+    }
+}
@@ -0,0 +1,92 @@
+//! Helpers shared between every year's solutions: input handling, grids, graphs, geometry and
+//! the plumbing for running, checking and benchmarking days.
+
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{self, BufRead, BufReader},
+};
+
+use error::{InputError, ParseError};
+pub use input::{Input, Token};
+
+pub mod bench;
+pub mod bigint;
+pub mod error;
+pub mod export;
+pub mod fetch;
+pub mod generator;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod hash;
+pub mod history;
+pub mod input;
+pub mod ledger;
+pub mod memory;
+pub mod parallel;
+pub mod parser;
+pub mod property;
+pub mod runner;
+pub mod scaffold;
+pub mod tool;
+pub mod trace;
+
+/// Input path which refers to stdin rather than a file.
+pub const STDIN_PATH: &str = "-";
+
+/// Opens a buffered reader over an input file, or stdin if the path is `-`.
+pub fn open_input(input_path: &str) -> io::Result<Box<dyn BufRead>> {
+    if input_path == STDIN_PATH {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        let file = File::open(input_path)?;
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+/// Reads the whole input file (or stdin if the path is `-`) into a string.
+pub fn read_input(input_path: &str) -> io::Result<String> {
+    let mut input = String::new();
+    open_input(input_path)?.read_to_string(&mut input)?;
+
+    Ok(input)
+}
+
+/// A puzzle solution, split into a parsing stage and a solver for each part.
+///
+/// Parsing happens once, so the parsed input can be shared between both parts (or reused by
+/// tests and benchmarks without touching the filesystem).
+pub trait Solution {
+    /// Parsed representation of the puzzle input.
+    type Input;
+
+    type Answer1: Display;
+    type Answer2: Display;
+
+    /// Number of parts in the puzzle (the last day only has one).
+    const PARTS: usize = 2;
+
+    /// Parses the puzzle input, returning an error that points at any malformed tokens.
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+
+    /// Parses the puzzle input from any buffered reader, e.g. a file or stdin.
+    fn parse_reader(mut reader: impl BufRead) -> Result<Self::Input, InputError> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+
+        Ok(Self::parse(&input)?)
+    }
+
+    /// Parses the puzzle input text and solves part 1.
+    fn solve_part_1(input: &str) -> Result<Self::Answer1, ParseError> {
+        Ok(Self::part1(&Self::parse(input)?))
+    }
+
+    /// Parses the puzzle input text and solves part 2.
+    fn solve_part_2(input: &str) -> Result<Self::Answer2, ParseError> {
+        Ok(Self::part2(&Self::parse(input)?))
+    }
+}
@@ -0,0 +1,116 @@
+//! Small scoped-thread executor for running independent work in parallel.
+//!
+//! Results always come back in the same order as the inputs, so output stays deterministic no
+//! matter how many threads are used. The thread count is set once with [`set_jobs`] (e.g. from the
+//! `--jobs` flag) and defaults to the number of available CPUs.
+
+use std::{
+    cell::Cell,
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+/// Configured number of threads, or 0 to use the number of available CPUs.
+static JOBS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Whether the current thread is one of the executor's workers.
+    static IN_WORKER: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Sets the maximum number of threads used by [`map`].
+pub fn set_jobs(jobs: NonZeroUsize) {
+    JOBS.store(jobs.get(), Ordering::Relaxed);
+}
+
+/// Maximum number of threads used by [`map`].
+pub fn jobs() -> usize {
+    match JOBS.load(Ordering::Relaxed) {
+        0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        jobs => jobs,
+    }
+}
+
+/// Applies a function to every item in parallel, returning the results in the same order.
+///
+/// Work is handed out one item at a time, so uneven workloads still balance across threads.
+/// Nested calls (e.g. a day fanning out its records while days are already running in parallel)
+/// run on the calling worker thread, rather than multiplying the number of threads.
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let thread_count = jobs().min(items.len());
+    if thread_count <= 1 || IN_WORKER.get() {
+        return items.iter().map(f).collect();
+    }
+
+    let next_idx = AtomicUsize::new(0);
+    let worker = || {
+        IN_WORKER.set(true);
+
+        let mut results = Vec::new();
+        loop {
+            let idx = next_idx.fetch_add(1, Ordering::Relaxed);
+            let Some(item) = items.get(idx) else {
+                break;
+            };
+
+            results.push((idx, f(item)));
+        }
+
+        results
+    };
+
+    let mut indexed_results: Vec<(usize, R)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..thread_count).map(|_| scope.spawn(worker)).collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("Worker thread panicked"))
+            .collect()
+    });
+
+    indexed_results.sort_unstable_by_key(|(idx, _)| *idx);
+    indexed_results
+        .into_iter()
+        .map(|(_, result)| result)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_input_order() {
+        let items: Vec<u64> = (0..100).collect();
+        let squares = map(&items, |&x| {
+            // Make earlier items slower, so they finish out of order
+            thread::sleep(std::time::Duration::from_micros(100 - x));
+            x * x
+        });
+
+        assert_eq!(squares, items.iter().map(|x| x * x).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn runs_nested_calls_inline() {
+        let outer: Vec<usize> = (0..4).collect();
+        let totals = map(&outer, |&i| {
+            let inner: Vec<usize> = (0..=i).collect();
+            map(&inner, |&j| (IN_WORKER.get() || jobs() == 1, j))
+                .into_iter()
+                .map(|(inline, j)| {
+                    assert!(inline);
+                    j
+                })
+                .sum::<usize>()
+        });
+
+        assert_eq!(totals, vec![0, 1, 3, 6]);
+    }
+}
@@ -0,0 +1,494 @@
+//! Small parser-combinator library for single-line puzzle formats.
+//!
+//! Parsers work on a [`Cursor`] over one line of the input. Everything they return borrows from
+//! that line, so any token (or [`spanned`] group of tokens) can be used to point a [`ParseError`]
+//! at the right column.
+//!
+//! ```
+//! use advent_of_code_common::parser::*;
+//!
+//! let mut size = (integer::<usize>(), literal("x"), integer::<usize>());
+//! let (width, _, height) = parse_line(1, "12x5", &mut size).unwrap();
+//! assert_eq!((width, height), (12, 5));
+//! ```
+
+use std::str::FromStr;
+
+use crate::error::ParseError;
+
+/// Position within a line of the input.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cursor<'a> {
+    line_number: usize,
+    line: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> Cursor<'a> {
+    pub const fn new(line_number: usize, line: &'a str) -> Self {
+        Cursor {
+            line_number,
+            line,
+            rest: line,
+        }
+    }
+
+    pub const fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    pub const fn line(&self) -> &'a str {
+        self.line
+    }
+
+    /// The remaining unparsed text.
+    pub const fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    /// Creates an error pointing at a token within the line.
+    pub fn error(&self, token: &'a str, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.line_number, self.line, token, message)
+    }
+
+    /// Creates an error pointing at the next character (or the end of the line).
+    pub fn error_here(&self, message: impl Into<String>) -> ParseError {
+        let next_char_len = self.rest.chars().next().map_or(0, char::len_utf8);
+        self.error(&self.rest[..next_char_len], message)
+    }
+
+    /// Consumes the next `len` bytes, returning them.
+    fn advance(&mut self, len: usize) -> &'a str {
+        let (consumed, rest) = self.rest.split_at(len);
+        self.rest = rest;
+
+        consumed
+    }
+
+    /// Text consumed since an earlier position of this cursor.
+    fn consumed_since(&self, earlier: &Cursor<'a>) -> &'a str {
+        &earlier.rest[..(earlier.rest.len() - self.rest.len())]
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    /// Checks that only whitespace is left in the line.
+    pub fn finish(mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+
+        if self.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error(self.rest, "Unexpected trailing input"))
+        }
+    }
+}
+
+/// Something that parses a value from the cursor, advancing past the consumed text.
+///
+/// Implemented for closures, and for tuples of parsers (which run in sequence).
+pub trait Parser<'a, T> {
+    fn parse(&mut self, cursor: &mut Cursor<'a>) -> Result<T, ParseError>;
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: FnMut(&mut Cursor<'a>) -> Result<T, ParseError>,
+{
+    fn parse(&mut self, cursor: &mut Cursor<'a>) -> Result<T, ParseError> {
+        self(cursor)
+    }
+}
+
+macro_rules! impl_tuple_parser {
+    ($($parser:ident: $value:ident),+) => {
+        impl<'a, $($value,)+ $($parser,)+> Parser<'a, ($($value,)+)> for ($($parser,)+)
+        where
+            $($parser: Parser<'a, $value>,)+
+        {
+            #[allow(non_snake_case)]
+            fn parse(&mut self, cursor: &mut Cursor<'a>) -> Result<($($value,)+), ParseError> {
+                let ($($parser,)+) = self;
+                Ok(($($parser.parse(cursor)?,)+))
+            }
+        }
+    };
+}
+
+impl_tuple_parser!(P1: T1, P2: T2);
+impl_tuple_parser!(P1: T1, P2: T2, P3: T3);
+impl_tuple_parser!(P1: T1, P2: T2, P3: T3, P4: T4);
+impl_tuple_parser!(P1: T1, P2: T2, P3: T3, P4: T4, P5: T5);
+
+/// Runs a parser over a whole line, which must not have anything left over afterwards.
+pub fn parse_line<'a, T>(
+    line_number: usize,
+    line: &'a str,
+    parser: &mut impl Parser<'a, T>,
+) -> Result<T, ParseError> {
+    let mut cursor = Cursor::new(line_number, line);
+    let value = parser.parse(&mut cursor)?;
+    cursor.finish()?;
+
+    Ok(value)
+}
+
+/// Matches an exact piece of text.
+pub fn literal<'a>(expected: &'static str) -> impl Parser<'a, &'a str> {
+    move |cursor: &mut Cursor<'a>| {
+        if cursor.rest.starts_with(expected) {
+            Ok(cursor.advance(expected.len()))
+        } else {
+            Err(cursor.error_here(format!("Expected '{}'", expected)))
+        }
+    }
+}
+
+/// Matches one character from a set.
+pub fn one_of<'a>(chars: &'static str) -> impl Parser<'a, char> {
+    move |cursor: &mut Cursor<'a>| match cursor.rest.chars().next() {
+        Some(c) if chars.contains(c) => {
+            cursor.advance(c.len_utf8());
+            Ok(c)
+        }
+        _ => Err(cursor.error_here(format!("Expected one of '{}'", chars))),
+    }
+}
+
+/// Matches one or more characters satisfying a predicate, e.g. `take_while1(char::is_alphabetic,
+/// "name")`.
+pub fn take_while1<'a>(
+    predicate: impl Fn(char) -> bool,
+    description: &'static str,
+) -> impl Parser<'a, &'a str> {
+    move |cursor: &mut Cursor<'a>| {
+        let len = cursor
+            .rest
+            .find(|c: char| !predicate(c))
+            .unwrap_or(cursor.rest.len());
+
+        if len == 0 {
+            Err(cursor.error_here(format!("Expected {}", description)))
+        } else {
+            Ok(cursor.advance(len))
+        }
+    }
+}
+
+/// Matches an integer (digits with an optional leading `-`), parsed as any numeric type.
+pub fn integer<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |cursor: &mut Cursor<'a>| {
+        let sign_len = usize::from(cursor.rest.starts_with('-'));
+        let digit_len = cursor.rest[sign_len..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(cursor.rest.len() - sign_len);
+
+        if digit_len == 0 {
+            return Err(cursor.error_here("Expected number"));
+        }
+
+        let start = *cursor;
+        let token = cursor.advance(sign_len + digit_len);
+
+        token
+            .parse::<T>()
+            .map_err(|_| start.error(token, "Invalid number"))
+    }
+}
+
+/// Skips any whitespace before running a parser.
+pub fn ws<'a, T>(mut parser: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |cursor: &mut Cursor<'a>| {
+        cursor.skip_whitespace();
+        parser.parse(cursor)
+    }
+}
+
+/// Transforms the value returned by a parser.
+pub fn map<'a, T, U>(
+    mut parser: impl Parser<'a, T>,
+    mut f: impl FnMut(T) -> U,
+) -> impl Parser<'a, U> {
+    move |cursor: &mut Cursor<'a>| parser.parse(cursor).map(&mut f)
+}
+
+/// Returns the parsed value along with the text it was parsed from, for pointing errors at it.
+pub fn spanned<'a, T>(mut parser: impl Parser<'a, T>) -> impl Parser<'a, (T, &'a str)> {
+    move |cursor: &mut Cursor<'a>| {
+        let start = *cursor;
+        let value = parser.parse(cursor)?;
+
+        Ok((value, cursor.consumed_since(&start)))
+    }
+}
+
+/// Runs a parser if it matches, or returns `None` without consuming anything.
+///
+/// If the parser fails partway through (i.e. the error doesn't point at where it started,
+/// ignoring whitespace), the error is returned instead, since the input is malformed rather than
+/// absent.
+pub fn opt<'a, T>(mut parser: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    move |cursor: &mut Cursor<'a>| {
+        let start = *cursor;
+
+        match parser.parse(cursor) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if failed_at_start(&start, &e) => {
+                *cursor = start;
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Runs a parser as many times as it matches (possibly zero).
+pub fn many0<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    let mut item = opt(parser);
+
+    move |cursor: &mut Cursor<'a>| {
+        let mut values = Vec::new();
+        while let Some(value) = item.parse(cursor)? {
+            values.push(value);
+        }
+
+        Ok(values)
+    }
+}
+
+/// Runs a parser as many times as it matches (at least once).
+pub fn many1<'a, T>(mut parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |cursor: &mut Cursor<'a>| {
+        let mut values = vec![parser.parse(cursor)?];
+
+        loop {
+            let start = *cursor;
+            match parser.parse(cursor) {
+                Ok(value) => values.push(value),
+                Err(e) if failed_at_start(&start, &e) => {
+                    *cursor = start;
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+/// Matches one or more items separated by a literal, e.g. `1,2,3`.
+pub fn separated_list1<'a, T>(
+    mut item: impl Parser<'a, T>,
+    separator: &'static str,
+) -> impl Parser<'a, Vec<T>> {
+    let mut separator = literal(separator);
+
+    move |cursor: &mut Cursor<'a>| {
+        let mut values = vec![item.parse(cursor)?];
+
+        loop {
+            let before_separator = *cursor;
+            if separator.parse(cursor).is_err() {
+                *cursor = before_separator;
+                break;
+            }
+
+            values.push(item.parse(cursor)?);
+        }
+
+        Ok(values)
+    }
+}
+
+/// Like [`separated_list1`], but also allows a single separator after the last item, e.g.
+/// `1,2,3,`.
+pub fn separated_list1_trailing<'a, T>(
+    mut item: impl Parser<'a, T>,
+    separator: &'static str,
+) -> impl Parser<'a, Vec<T>> {
+    let mut separator = literal(separator);
+
+    move |cursor: &mut Cursor<'a>| {
+        let mut values = vec![item.parse(cursor)?];
+
+        loop {
+            let before_separator = *cursor;
+            if separator.parse(cursor).is_err() {
+                *cursor = before_separator;
+                break;
+            }
+
+            // Nothing after the separator means it was the trailing one
+            let after_separator = *cursor;
+            match item.parse(cursor) {
+                Ok(value) => values.push(value),
+                Err(e) if failed_at_start(&after_separator, &e) => {
+                    *cursor = after_separator;
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+/// Matches a parser surrounded by opening and closing literals, e.g. `(1,3)`.
+pub fn bracketed<'a, T>(
+    open: &'static str,
+    mut inner: impl Parser<'a, T>,
+    close: &'static str,
+) -> impl Parser<'a, T> {
+    let mut open = literal(open);
+    let mut close = literal(close);
+
+    move |cursor: &mut Cursor<'a>| {
+        open.parse(cursor)?;
+        let value = inner.parse(cursor)?;
+        close.parse(cursor)?;
+
+        Ok(value)
+    }
+}
+
+/// Whether a parser failed at its starting position (ignoring whitespace), i.e. whether it failed
+/// without matching anything.
+fn failed_at_start(start: &Cursor, error: &ParseError) -> bool {
+    let offset = start.line.len() - start.rest.trim_start().len();
+    let column = start.line[..offset].chars().count() + 1;
+
+    error.line == start.line_number && error.column == column
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sequences() {
+        let mut range = (integer::<u64>(), literal("-"), integer::<u64>());
+        assert_eq!(parse_line(1, "11-22", &mut range).unwrap(), (11, "-", 22));
+
+        let error = parse_line(1, "11-22x", &mut range).unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (6, "Unexpected trailing input")
+        );
+
+        let error = parse_line(1, "11+22", &mut range).unwrap_err();
+        assert_eq!((error.column, error.message.as_str()), (3, "Expected '-'"));
+    }
+
+    #[test]
+    fn parses_integers() {
+        assert_eq!(parse_line(1, "-12", &mut integer::<i32>()).unwrap(), -12);
+
+        let error = parse_line(1, "-12", &mut integer::<u32>()).unwrap_err();
+        assert_eq!(
+            (error.token.as_str(), error.message.as_str()),
+            ("-12", "Invalid number")
+        );
+
+        let error = parse_line(1, "x", &mut integer::<u32>()).unwrap_err();
+        assert_eq!(error.message, "Expected number");
+    }
+
+    #[test]
+    fn parses_bracketed_lists() {
+        let mut groups = many1(ws(bracketed(
+            "(",
+            separated_list1(integer::<u8>(), ","),
+            ")",
+        )));
+
+        assert_eq!(
+            parse_line(1, "(3) (1,3)  (0,2) ", &mut groups).unwrap(),
+            vec![vec![3], vec![1, 3], vec![0, 2]]
+        );
+
+        // Errors inside a group aren't swallowed by `many1`
+        let error = parse_line(1, "(3) (1,x)", &mut groups).unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (8, "Expected number")
+        );
+
+        let error = parse_line(1, "{3}", &mut groups).unwrap_err();
+        assert_eq!((error.column, error.message.as_str()), (1, "Expected '('"));
+    }
+
+    #[test]
+    fn tracks_spans() {
+        let mut lights = ws(spanned(bracketed(
+            "[",
+            many1(map(one_of(".#"), |c| c == '#')),
+            "]",
+        )));
+
+        let line = "  [.##.] (3)";
+        let mut cursor = Cursor::new(4, line);
+        let (lights, span) = lights.parse(&mut cursor).unwrap();
+
+        assert_eq!(lights, vec![false, true, true, false]);
+        assert_eq!(span, "[.##.]");
+        assert_eq!(cursor.error(span, "Test").column, 3);
+        assert_eq!(cursor.rest(), " (3)");
+    }
+
+    #[test]
+    fn optional_items() {
+        // List with an optional trailing separator
+        let mut list = many1(map(
+            (
+                take_while1(|c| c.is_ascii_alphabetic(), "name"),
+                opt(literal(",")),
+            ),
+            |(name, _)| name,
+        ));
+
+        assert_eq!(
+            parse_line(1, "ab,cd,", &mut list).unwrap(),
+            vec!["ab", "cd"]
+        );
+        assert_eq!(parse_line(1, "ab", &mut list).unwrap(), vec!["ab"]);
+
+        let error = parse_line(1, "ab,,", &mut list).unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (4, "Unexpected trailing input")
+        );
+    }
+
+    #[test]
+    fn separated_lists_with_a_trailing_separator() {
+        let mut list = separated_list1_trailing(integer::<u8>(), ",");
+
+        assert_eq!(parse_line(1, "1,2,3", &mut list).unwrap(), vec![1, 2, 3]);
+        assert_eq!(parse_line(1, "1,2,3,", &mut list).unwrap(), vec![1, 2, 3]);
+
+        let error = parse_line(1, "1,2,,", &mut list).unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (5, "Unexpected trailing input")
+        );
+
+        let error = parse_line(1, "1,2 3", &mut list).unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (5, "Unexpected trailing input")
+        );
+
+        // Items that fail partway through are still errors
+        let mut ranges =
+            separated_list1_trailing((integer::<u8>(), literal("-"), integer::<u8>()), ",");
+        let error = parse_line(1, "1-2,3-", &mut ranges).unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (7, "Expected number")
+        );
+    }
+}
@@ -0,0 +1,274 @@
+//! Property testing over generated inputs, with shrinking to a minimal counterexample.
+//!
+//! The main use is differential testing: a day's solver is run against a naive reference
+//! implementation on thousands of small generated inputs (see [`check_against_reference`]). When
+//! they disagree, the input is shrunk by removing lines and comma-separated items and making
+//! numbers smaller, for as long as the failure still reproduces.
+
+use std::{
+    fmt::{self, Display},
+    mem,
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::{
+    generator::{Generator, Rng},
+    runner::Day,
+};
+
+/// Upper limit on shrinking steps, so a failure that depends on a large number still finishes.
+const MAX_SHRINK_STEPS: usize = 10_000;
+
+/// How many generated inputs to check, and how big they get.
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    /// Seed of the first case, incremented for each case after it.
+    pub seed: u64,
+    pub cases: usize,
+    /// Sizes cycle from 1 up to this, so small inputs are always covered.
+    pub max_size: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            seed: 0,
+            cases: 1000,
+            max_size: 8,
+        }
+    }
+}
+
+/// Result of checking a property against one input.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    Pass,
+    /// The input doesn't apply, e.g. because shrinking made it unparseable.
+    Discard,
+    Mismatch(String),
+    Panic(String),
+}
+
+/// Smallest failing input found for a property.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Counterexample {
+    /// Seed and size of the generated input that first failed.
+    pub seed: u64,
+    pub size: usize,
+    pub input: String,
+    pub outcome: Outcome,
+}
+
+impl Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match &self.outcome {
+            Outcome::Mismatch(message) => message.as_str(),
+            Outcome::Panic(message) => message.as_str(),
+            Outcome::Pass | Outcome::Discard => "No failure",
+        };
+
+        write!(
+            f,
+            "Failed with seed {} (size {}), shrunk to:\n{}\n{}",
+            self.seed, self.size, self.input, message
+        )
+    }
+}
+
+/// Checks a property against generated inputs, returning the number of inputs it applied to.
+pub fn check(
+    generate: Generator,
+    config: Config,
+    property: impl Fn(&str) -> Outcome,
+) -> Result<usize, Counterexample> {
+    let mut checked = 0;
+
+    for case in 0..config.cases {
+        let seed = config.seed.wrapping_add(case as u64);
+        let size = 1 + case % config.max_size.max(1);
+        let input = generate(&mut Rng::new(seed), size);
+
+        match property(&input) {
+            Outcome::Pass => checked += 1,
+            Outcome::Discard => {}
+            outcome => {
+                let input = shrink(&input, |candidate| {
+                    mem::discriminant(&property(candidate)) == mem::discriminant(&outcome)
+                });
+
+                return Err(Counterexample {
+                    seed,
+                    size,
+                    outcome: property(&input),
+                    input,
+                });
+            }
+        }
+    }
+
+    Ok(checked)
+}
+
+/// Property that a day's answer for one part matches a reference solver's answer.
+///
+/// Inputs the day can't parse are discarded, and panics in either solver are failures.
+pub fn check_against_reference(
+    day: &Day,
+    part: usize,
+    reference: fn(&str) -> String,
+) -> impl Fn(&str) -> Outcome {
+    move |input| {
+        let Ok(parsed) = (day.parse)(input) else {
+            return Outcome::Discard;
+        };
+
+        let answers =
+            panic::catch_unwind(AssertUnwindSafe(|| (parsed.solve(part), reference(input))));
+
+        match answers {
+            Ok((answer, expected)) if answer == expected => Outcome::Pass,
+            Ok((answer, expected)) => Outcome::Mismatch(format!(
+                "Day {} part {} answered {}, but the reference answered {}",
+                day.number, part, answer, expected
+            )),
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+
+                Outcome::Panic(format!(
+                    "Day {} part {} panicked: {}",
+                    day.number, part, message
+                ))
+            }
+        }
+    }
+}
+
+/// Repeatedly replaces the input with the first smaller candidate that still fails.
+pub fn shrink(input: &str, still_fails: impl Fn(&str) -> bool) -> String {
+    let mut current = input.to_string();
+
+    for _ in 0..MAX_SHRINK_STEPS {
+        let smaller = shrink_candidates(&current).find(|c| still_fails(c));
+        match smaller {
+            Some(smaller) => current = smaller,
+            None => break,
+        }
+    }
+
+    current
+}
+
+/// Smaller variations of an input, from the most to the least aggressive.
+fn shrink_candidates(input: &str) -> impl Iterator<Item = String> + '_ {
+    let lines: Vec<&str> = input.lines().collect();
+    let join = |lines: &[&str]| {
+        let mut text = lines.join("\n");
+        text.push('\n');
+        text
+    };
+
+    // Remove runs of lines, halving the run length each time
+    let mut candidates = Vec::new();
+    let mut run = lines.len() / 2;
+    while run > 0 {
+        for start in (0..=(lines.len() - run)).step_by(run) {
+            let mut remaining = lines.clone();
+            remaining.drain(start..(start + run));
+            candidates.push(join(&remaining));
+        }
+
+        run /= 2;
+    }
+
+    // Remove comma-separated items within a line
+    for (line_idx, line) in lines.iter().enumerate() {
+        let items: Vec<&str> = line.split(',').collect();
+        if items.len() < 2 {
+            continue;
+        }
+
+        for item_idx in 0..items.len() {
+            let mut remaining_items = items.clone();
+            remaining_items.remove(item_idx);
+            let shrunk_line = remaining_items.join(",");
+
+            let mut shrunk_lines = lines.clone();
+            shrunk_lines[line_idx] = &shrunk_line;
+            candidates.push(join(&shrunk_lines));
+        }
+    }
+
+    // Make each number smaller, halving first then decrementing
+    let mut number_start = None;
+    for (idx, c) in input.char_indices().chain([(input.len(), ' ')]) {
+        match (c.is_ascii_digit(), number_start) {
+            (true, None) => number_start = Some(idx),
+            (false, Some(start)) => {
+                number_start = None;
+
+                let Ok(number) = input[start..idx].parse::<u64>() else {
+                    continue;
+                };
+
+                for smaller in [number / 2, number.saturating_sub(1)] {
+                    if smaller < number {
+                        candidates.push(format!("{}{}{}", &input[..start], smaller, &input[idx..]));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    candidates.into_iter().filter(move |c| c.as_str() != input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| format!("{},{}\n", rng.range(0..=100), rng.range(0..=100)))
+            .collect()
+    }
+
+    #[test]
+    fn passes_when_property_holds() {
+        let config = Config {
+            cases: 50,
+            ..Config::default()
+        };
+
+        assert_eq!(check(numbers, config, |_| Outcome::Pass), Ok(50));
+        assert_eq!(check(numbers, config, |_| Outcome::Discard), Ok(0));
+    }
+
+    #[test]
+    fn shrinks_to_minimal_counterexample() {
+        // Fails whenever any number is at least 37
+        let property = |input: &str| {
+            let too_big = input
+                .split(|c: char| !c.is_ascii_digit())
+                .filter_map(|n| n.parse::<u64>().ok())
+                .any(|n| n >= 37);
+
+            if too_big {
+                Outcome::Mismatch("Too big".to_string())
+            } else {
+                Outcome::Pass
+            }
+        };
+
+        let counterexample = check(numbers, Config::default(), property).unwrap_err();
+        assert_eq!(counterexample.input, "37\n");
+        assert_eq!(
+            counterexample.outcome,
+            Outcome::Mismatch("Too big".to_string())
+        );
+    }
+}
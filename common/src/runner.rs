@@ -0,0 +1,764 @@
+use std::{
+    env,
+    io::BufRead,
+    num::NonZeroUsize,
+    path::Path,
+    process,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::{InputError, ParseError},
+    export::Exporter,
+    fetch,
+    generator::Generator,
+    hash::{HashingReader, sha256_hex},
+    history::{self, HISTORY_PATH, HistoryRecord},
+    ledger::{ANSWERS_PATH, LedgerMode, LedgerSession},
+    parallel,
+    tool::Tool,
+    trace::{self, Level},
+    *,
+};
+
+/// Parsed puzzle input for a day, with type-erased solvers for each part.
+pub trait ParsedInput {
+    /// Solves the given part, returning the formatted answer.
+    fn solve(&self, part: usize) -> String;
+}
+
+struct Parsed<S: Solution>(S::Input);
+
+impl<S: Solution> ParsedInput for Parsed<S> {
+    fn solve(&self, part: usize) -> String {
+        match part {
+            1 => S::part1(&self.0).to_string(),
+            2 => S::part2(&self.0).to_string(),
+            _ => panic!("Unexpected part {}", part),
+        }
+    }
+}
+
+fn parse_erased<S: Solution + 'static>(input: &str) -> Result<Box<dyn ParsedInput>, ParseError> {
+    Ok(Box::new(Parsed::<S>(S::parse(input)?)))
+}
+
+/// A puzzle day and the type-erased entry points for its [`Solution`].
+pub struct Day {
+    pub number: u8,
+    pub parts: usize,
+    pub parse: fn(&str) -> Result<Box<dyn ParsedInput>, ParseError>,
+    /// Generates random inputs in this day's format, if supported.
+    pub generate: Option<Generator>,
+    /// Exports intermediate state as CSV, if supported.
+    pub export: Option<Exporter>,
+    /// Solves every part while reading the input, if supported.
+    pub stream: Option<Streamer>,
+    /// Extra subcommand for exploring the puzzle, if any.
+    pub tool: Option<Tool>,
+}
+
+/// Solves every part of a day while reading its input, rather than parsing it all up front, so
+/// inputs of any size can be handled. Returns the formatted answer to each part, in order.
+pub type Streamer = fn(&mut dyn BufRead) -> Result<Vec<String>, InputError>;
+
+/// Creates the entry for a day from its [`Solution`].
+pub const fn day<S: Solution + 'static>(number: u8) -> Day {
+    Day {
+        number,
+        parts: S::PARTS,
+        parse: parse_erased::<S>,
+        generate: None,
+        export: None,
+        stream: None,
+        tool: None,
+    }
+}
+
+impl Day {
+    /// Registers a generator for random inputs, used by `aoc gen`.
+    pub const fn with_generator(self, generate: Generator) -> Day {
+        Day {
+            generate: Some(generate),
+            ..self
+        }
+    }
+
+    /// Registers a streaming solver, which is used instead of parsing the whole input when
+    /// running the day (but not when benchmarking it).
+    pub const fn with_streamer(self, stream: Streamer) -> Day {
+        Day {
+            stream: Some(stream),
+            ..self
+        }
+    }
+
+    /// Registers CSV exports of the day's intermediate state, used by `aoc export`.
+    pub const fn with_exporter(self, export: Exporter) -> Day {
+        Day {
+            export: Some(export),
+            ..self
+        }
+    }
+
+    /// Registers an extra subcommand, run as `aoc <name>`.
+    pub const fn with_tool(self, tool: Tool) -> Day {
+        Day {
+            tool: Some(tool),
+            ..self
+        }
+    }
+}
+
+/// A year's worth of solved days.
+pub struct Year {
+    pub number: u16,
+    /// Solved days, in order.
+    pub days: &'static [Day],
+}
+
+impl Year {
+    /// Looks up a day by its number.
+    pub fn find_day(&self, number: u8) -> Option<&'static Day> {
+        self.days.iter().find(|d| d.number == number)
+    }
+
+    /// Looks up a tool registered by one of the days.
+    pub fn find_tool(&self, name: &str) -> Option<&'static Tool> {
+        self.days
+            .iter()
+            .filter_map(|d| d.tool.as_ref())
+            .find(|t| t.name == name)
+    }
+}
+
+/// Options shared by `aoc run` and the individual `dayN` binaries.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Part to run, or all parts if not specified.
+    pub part: Option<usize>,
+    pub ledger_mode: Option<LedgerMode>,
+    /// Number of iterations to benchmark each stage for, if benchmarking.
+    pub bench_iterations: Option<usize>,
+    /// Print benchmark results as a Markdown table row.
+    pub markdown: bool,
+    /// Append benchmark results to the timing history.
+    pub save_history: bool,
+    pub format: OutputFormat,
+    /// Maximum number of threads to use, or the number of CPUs if not specified.
+    pub jobs: Option<NonZeroUsize>,
+    /// Most detailed level of solver events to log to stderr, if any.
+    pub trace_level: Option<Level>,
+}
+
+/// How answers are printed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    /// One JSON object per line for each part, see [`PartRecord`].
+    Json,
+}
+
+/// Result of solving one part of a day, printed as a line of JSON by `--format json`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct PartRecord<'a> {
+    pub day: u8,
+    pub part: usize,
+    pub answer: &'a str,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    pub input_sha256: &'a str,
+    /// Ledger verdict, if answers are being checked.
+    pub verdict: Option<String>,
+}
+
+impl PartRecord<'_> {
+    pub fn to_json(&self) -> String {
+        let mut json = format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"parse_ms\":{:.3},\"solve_ms\":{:.3},\"input_sha256\":{}",
+            self.day,
+            self.part,
+            json_string(self.answer),
+            self.parse_time.as_secs_f64() * 1000.0,
+            self.solve_time.as_secs_f64() * 1000.0,
+            json_string(self.input_sha256),
+        );
+
+        if let Some(verdict) = &self.verdict {
+            json.push_str(&format!(",\"verdict\":{}", json_string(verdict)));
+        }
+
+        json.push('}');
+        json
+    }
+}
+
+/// Quotes a string for JSON, escaping any special characters.
+fn json_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+/// Usage text for the options accepted by [`RunOptions::parse_flag`].
+pub const OPTIONS_USAGE: &str = "\
+Options:
+  --part 1|2          Only run one part
+  --record            Check answers against answers.toml, and record any new answers
+  --verify            Check answers against answers.toml without modifying it
+  --bench             Time parsing and each part instead of printing the answers
+  --iterations N      Number of benchmark iterations (default: 100, implies --bench)
+  --markdown          Print benchmark results as a Markdown table
+  --save-history      Append benchmark results to bench_history.csv, keyed by git commit
+  --format text|json  Print answers as text (default), or as one JSON object per part
+  --jobs N            Maximum number of threads to use (default: number of CPUs)
+  -v, -vv             Log solver events to stderr (-v for summaries, -vv for every step)";
+
+impl RunOptions {
+    /// Parses an option flag, taking its value from the remaining arguments if it needs one.
+    ///
+    /// Returns `Ok(false)` if the argument isn't an option flag.
+    pub fn parse_flag<'a>(
+        &mut self,
+        arg: &str,
+        args: &mut impl Iterator<Item = &'a String>,
+    ) -> Result<bool, String> {
+        let mut flag_value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+
+        match arg {
+            "--part" => {
+                let part_str = flag_value()?;
+                self.part = match part_str.as_str() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    _ => return Err(format!("Invalid part '{}'", part_str)),
+                };
+            }
+            "--record" => self.ledger_mode = Some(LedgerMode::Record),
+            "--verify" => self.ledger_mode = Some(LedgerMode::Verify),
+            "--bench" => {
+                self.bench_iterations
+                    .get_or_insert(bench::DEFAULT_ITERATIONS);
+            }
+            "--iterations" => {
+                let iterations_str = flag_value()?;
+                let iterations = iterations_str
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&i| i > 0)
+                    .ok_or_else(|| format!("Invalid iteration count '{}'", iterations_str))?;
+
+                self.bench_iterations = Some(iterations);
+            }
+            "--markdown" => self.markdown = true,
+            "--save-history" => self.save_history = true,
+            "--jobs" => {
+                let jobs_str = flag_value()?;
+                let jobs = jobs_str
+                    .parse::<NonZeroUsize>()
+                    .map_err(|_| format!("Invalid job count '{}'", jobs_str))?;
+
+                self.jobs = Some(jobs);
+            }
+            "-v" | "--verbose" => self.trace_level = self.trace_level.max(Some(Level::Debug)),
+            "-vv" => self.trace_level = Some(Level::Trace),
+            "--format" => {
+                let format_str = flag_value()?;
+                self.format = match format_str.as_str() {
+                    "text" => OutputFormat::Text,
+                    "json" => OutputFormat::Json,
+                    _ => return Err(format!("Invalid format '{}'", format_str)),
+                };
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    /// Checks that the options can be used together.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.bench_iterations.is_some() && self.ledger_mode.is_some() {
+            return Err("--bench can't be combined with --record or --verify".to_string());
+        }
+
+        if self.markdown && self.bench_iterations.is_none() {
+            return Err("--markdown can only be used with --bench".to_string());
+        }
+
+        if self.save_history && self.bench_iterations.is_none() {
+            return Err("--save-history can only be used with --bench".to_string());
+        }
+
+        if self.format == OutputFormat::Json && self.bench_iterations.is_some() {
+            return Err("--format json can't be combined with --bench".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Whether output should be Markdown table rows, rather than a section per day.
+    pub fn is_markdown_table(&self) -> bool {
+        self.markdown && self.bench_iterations.is_some()
+    }
+
+    /// Applies the process-wide settings: the `--jobs` limit for the [`parallel`] executor, and
+    /// the [`trace`] level.
+    pub fn apply(&self) {
+        if let Some(jobs) = self.jobs {
+            parallel::set_jobs(jobs);
+        }
+
+        trace::set_level(self.trace_level);
+    }
+
+    /// Whether each day's output starts with a `----- Day N -----` header.
+    pub fn prints_day_headers(&self) -> bool {
+        !self.is_markdown_table() && self.format == OutputFormat::Text
+    }
+}
+
+/// Answers (or benchmark results) for a day, computed separately from printing them so several
+/// days can be solved in parallel and still printed in order.
+pub enum DayOutput {
+    Bench(bench::BenchReport),
+    Answers {
+        input_hash: String,
+        parse_time: Duration,
+        parts: Vec<PartAnswer>,
+    },
+}
+
+/// Answer to one part of a day, and how long it took to solve.
+pub struct PartAnswer {
+    pub part: usize,
+    pub answer: String,
+    pub solve_time: Duration,
+}
+
+/// Solves the requested part of a day (or all parts if none is specified), or benchmarks it.
+///
+/// The input path can be `-` to read the puzzle input from stdin.
+pub fn solve_day(
+    day: &Day,
+    options: &RunOptions,
+    input_path: &str,
+) -> Result<DayOutput, InputError> {
+    if let (Some(stream), None) = (day.stream, options.bench_iterations) {
+        return stream_day(stream, options, open_input(input_path)?);
+    }
+
+    let input = read_input(input_path)?;
+
+    if let Some(iterations) = options.bench_iterations {
+        let report = bench::bench_day(day, &input, options.part, iterations)?;
+        return Ok(DayOutput::Bench(report));
+    }
+
+    let parse_start = Instant::now();
+    let parsed_input = (day.parse)(&input)?;
+    let parse_time = parse_start.elapsed();
+
+    let parts = (1..=day.parts)
+        .filter(|&part| options.part.is_none_or(|p| p == part))
+        .map(|part| {
+            let solve_start = Instant::now();
+            let answer = parsed_input.solve(part);
+
+            PartAnswer {
+                part,
+                answer,
+                solve_time: solve_start.elapsed(),
+            }
+        })
+        .collect();
+
+    Ok(DayOutput::Answers {
+        input_hash: sha256_hex(input.as_bytes()),
+        parse_time,
+        parts,
+    })
+}
+
+/// Solves a day with its [`Streamer`]. Parsing and solving happen together, so all of the time
+/// is counted as parsing.
+fn stream_day(
+    stream: Streamer,
+    options: &RunOptions,
+    reader: impl BufRead,
+) -> Result<DayOutput, InputError> {
+    let mut reader = HashingReader::new(reader);
+
+    let start = Instant::now();
+    let answers = stream(&mut reader)?;
+    let parse_time = start.elapsed();
+
+    let parts = answers
+        .into_iter()
+        .zip(1..)
+        .filter(|&(_, part)| options.part.is_none_or(|p| p == part))
+        .map(|(answer, part)| PartAnswer {
+            part,
+            answer,
+            solve_time: Duration::ZERO,
+        })
+        .collect();
+
+    Ok(DayOutput::Answers {
+        input_hash: reader.finish_hex()?,
+        parse_time,
+        parts,
+    })
+}
+
+/// Prints the output from [`solve_day`] in the requested format.
+///
+/// If a ledger session is provided, each answer is checked against the known answers for the
+/// input. Errors are passed back so the caller can report them.
+pub fn print_day(
+    year: &Year,
+    day: &Day,
+    options: &RunOptions,
+    input_path: &str,
+    output: Result<DayOutput, InputError>,
+    mut ledger: Option<&mut LedgerSession>,
+) -> Result<(), InputError> {
+    if options.prints_day_headers() {
+        println!("----- Day {} -----", day.number);
+
+        if input_path == STDIN_PATH {
+            println!("Input File: <stdin>");
+        } else {
+            println!("Input File: {}", input_path);
+        }
+    }
+
+    let (input_hash, parse_time, parts) = match output? {
+        DayOutput::Bench(report) => {
+            if options.markdown {
+                println!("{}", report.markdown_row());
+            } else {
+                print!("{}", report);
+            }
+
+            if options.save_history {
+                save_history(year, &report);
+            }
+
+            return Ok(());
+        }
+        DayOutput::Answers {
+            input_hash,
+            parse_time,
+            parts,
+        } => (input_hash, parse_time, parts),
+    };
+
+    for PartAnswer {
+        part,
+        answer,
+        solve_time,
+    } in parts
+    {
+        let verdict = ledger
+            .as_deref_mut()
+            .map(|session| session.check(day.number, part, &input_hash, &answer));
+
+        match (options.format, verdict) {
+            (OutputFormat::Json, verdict) => {
+                let record = PartRecord {
+                    day: day.number,
+                    part,
+                    answer: &answer,
+                    parse_time,
+                    solve_time,
+                    input_sha256: &input_hash,
+                    verdict: verdict.map(|v| v.to_string()),
+                };
+                println!("{}", record.to_json());
+            }
+            (OutputFormat::Text, Some(verdict)) => {
+                println!("Part {}: {} [{}]", part, answer, verdict)
+            }
+            (OutputFormat::Text, None) => println!("Part {}: {}", part, answer),
+        }
+    }
+
+    Ok(())
+}
+
+/// Solves a day and prints the results, see [`solve_day`] and [`print_day`].
+pub fn run_day(
+    year: &Year,
+    day: &Day,
+    options: &RunOptions,
+    input_path: &str,
+    ledger: Option<&mut LedgerSession>,
+) -> Result<(), InputError> {
+    let output = solve_day(day, options, input_path);
+    print_day(year, day, options, input_path, output, ledger)
+}
+
+/// Finds the input file for a day within a directory, e.g. `day7.txt` or `day07.txt`.
+pub fn find_input_file(input_dir: &Path, day_number: u8) -> Option<String> {
+    [
+        format!("day{}.txt", day_number),
+        format!("day{:02}.txt", day_number),
+    ]
+    .iter()
+    .map(|name| input_dir.join(name))
+    .find(|path| path.is_file())
+    .map(|path| path.to_string_lossy().into_owned())
+}
+
+/// Shared `main` for the individual `dayN` binaries.
+///
+/// Usage: `dayN [options] [<input>]`, with the options described in [`OPTIONS_USAGE`]. If no input
+/// is given, the cached input downloaded by `aoc fetch` is used.
+pub fn day_main(year: &Year, day_number: u8) {
+    let day = year.find_day(day_number).expect("Day is not registered");
+
+    let exit_with_usage = |message: &str| -> ! {
+        eprintln!("{}", message);
+        eprintln!(
+            "Usage: day{} [options] [<input>]\n\n{}",
+            day.number, OPTIONS_USAGE
+        );
+        process::exit(1);
+    };
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut options = RunOptions::default();
+    let mut input_path: Option<&str> = None;
+
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        match options.parse_flag(arg, &mut args_iter) {
+            Ok(true) => {}
+            Ok(false) if input_path.is_none() => input_path = Some(arg),
+            Ok(false) => exit_with_usage(&format!("Unexpected argument '{}'", arg)),
+            Err(message) => exit_with_usage(&message),
+        }
+    }
+
+    if let Err(message) = options.validate() {
+        exit_with_usage(&message);
+    }
+
+    options.apply();
+
+    if options.part.is_some_and(|p| p > day.parts) {
+        exit_with_usage(&format!(
+            "Day {} has no part {}",
+            day.number,
+            options.part.unwrap()
+        ));
+    }
+
+    // Fall back to the cached input from `aoc fetch`
+    let cached_path;
+    let input_path = match input_path {
+        Some(input_path) => input_path,
+        None => {
+            cached_path =
+                fetch::cached_input_path(Path::new(fetch::INPUTS_DIR), year.number, day.number);
+            if !cached_path.is_file() {
+                println!("----- Day {} -----", day.number);
+                eprintln!(
+                    "No input file specified, and '{}' doesn't exist (use 'aoc fetch {}' to download it)",
+                    cached_path.display(),
+                    day.number
+                );
+                process::exit(1);
+            }
+
+            cached_path.to_str().expect("Cache path is valid UTF-8")
+        }
+    };
+
+    if options.is_markdown_table() {
+        println!("{}", bench::MARKDOWN_HEADER);
+    }
+
+    let mut ledger = options.ledger_mode.map(open_ledger);
+
+    if let Err(e) = run_day(year, day, &options, input_path, ledger.as_mut()) {
+        report_error(input_path, e);
+        process::exit(1);
+    }
+
+    if let Some(ledger) = ledger {
+        finish_ledger(ledger);
+    }
+}
+
+/// Appends benchmark results to the timing history, exiting if it can't be written.
+fn save_history(year: &Year, report: &bench::BenchReport) {
+    let records = HistoryRecord::from_report(
+        history::current_commit(),
+        history::current_timestamp(),
+        year.number,
+        report,
+    );
+
+    if let Err(e) = history::append(Path::new(HISTORY_PATH), &records) {
+        eprintln!("Unable to write timing history '{}': {}", HISTORY_PATH, e);
+        process::exit(1);
+    }
+}
+
+/// Opens the answer ledger in the working directory, exiting if it can't be read.
+pub fn open_ledger(mode: LedgerMode) -> LedgerSession {
+    LedgerSession::open(Path::new(ANSWERS_PATH), mode).unwrap_or_else(|e| {
+        match e {
+            InputError::Io(e) => {
+                eprintln!("Unable to read answer ledger '{}': {}", ANSWERS_PATH, e)
+            }
+            InputError::Parse(e) => eprintln!("{}", e.with_file(ANSWERS_PATH).render()),
+        }
+
+        process::exit(1);
+    })
+}
+
+/// Saves any newly recorded answers, and exits with an error if any answer didn't match.
+pub fn finish_ledger(ledger: LedgerSession) {
+    if let Err(e) = ledger.finish(Path::new(ANSWERS_PATH)) {
+        eprintln!("Unable to write answer ledger '{}': {}", ANSWERS_PATH, e);
+        process::exit(1);
+    }
+
+    if ledger.has_failures() {
+        process::exit(1);
+    }
+}
+
+/// Prints an input error to stderr, with a snippet of the offending line for parsing errors.
+pub fn report_error(input_path: &str, error: InputError) {
+    match error {
+        InputError::Io(e) => eprintln!("Unable to read input file '{}': {}", input_path, e),
+        InputError::Parse(e) => {
+            let file = if input_path == STDIN_PATH {
+                "<stdin>"
+            } else {
+                input_path
+            };
+
+            eprintln!("{}", e.with_file(file).render());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_part_records_as_json() {
+        let mut record = PartRecord {
+            day: 3,
+            part: 2,
+            answer: "3121910778619",
+            parse_time: Duration::from_micros(1250),
+            solve_time: Duration::from_nanos(500),
+            input_sha256: "ab12",
+            verdict: None,
+        };
+
+        assert_eq!(
+            record.to_json(),
+            r#"{"day":3,"part":2,"answer":"3121910778619","parse_ms":1.250,"solve_ms":0.001,"input_sha256":"ab12"}"#
+        );
+
+        record.answer = "a\"b\\\n";
+        record.verdict = Some("PASS".to_string());
+        assert!(record.to_json().contains(r#""answer":"a\"b\\\n","#));
+        assert!(record.to_json().ends_with(r#","verdict":"PASS"}"#));
+    }
+
+    #[test]
+    fn rejects_json_benchmarks() {
+        let args = ["--format", "json", "--bench"].map(String::from);
+        let mut options = RunOptions::default();
+
+        let mut args_iter = args.iter();
+        while let Some(arg) = args_iter.next() {
+            assert_eq!(options.parse_flag(arg, &mut args_iter), Ok(true));
+        }
+
+        assert_eq!(options.format, OutputFormat::Json);
+        assert!(options.validate().is_err());
+    }
+
+    #[test]
+    fn streams_days_that_support_it() {
+        // Sums the numbers before the first blank line, and counts them
+        let stream: Streamer = |reader| {
+            let numbers = reader
+                .lines()
+                .map_while(|line| line.ok().filter(|l| !l.is_empty()))
+                .map(|line| line.parse::<u64>().unwrap())
+                .collect::<Vec<_>>();
+
+            Ok(vec![
+                numbers.iter().sum::<u64>().to_string(),
+                numbers.len().to_string(),
+            ])
+        };
+
+        let input = "1\n2\n3\n\nnotes\n";
+        let options = RunOptions {
+            part: Some(2),
+            ..RunOptions::default()
+        };
+
+        let Ok(DayOutput::Answers {
+            input_hash, parts, ..
+        }) = stream_day(stream, &options, input.as_bytes())
+        else {
+            panic!("Expected answers");
+        };
+
+        assert_eq!(input_hash, sha256_hex(input.as_bytes()));
+        assert_eq!(parts.len(), 1);
+        assert_eq!((parts[0].part, parts[0].answer.as_str()), (2, "3"));
+    }
+
+    #[test]
+    fn parses_trace_levels() {
+        for (args, expected) in [
+            (vec![], None),
+            (vec!["-v"], Some(Level::Debug)),
+            (vec!["-vv"], Some(Level::Trace)),
+            (vec!["-vv", "--verbose"], Some(Level::Trace)),
+        ] {
+            let args: Vec<String> = args.into_iter().map(String::from).collect();
+            let mut options = RunOptions::default();
+
+            let mut args_iter = args.iter();
+            while let Some(arg) = args_iter.next() {
+                assert_eq!(options.parse_flag(arg, &mut args_iter), Ok(true));
+            }
+
+            assert_eq!(options.trace_level, expected);
+        }
+    }
+}
@@ -0,0 +1,665 @@
+//! Generates the skeleton for a new day, so every day starts from the same shape.
+//!
+//! `aoc new <year> <day>` creates `src/dayN.rs` and `src/bin/dayN.rs`, then registers the day in
+//! the year's `lib.rs`, `runner::DAYS`, `Cargo.toml` and the README table. If the year doesn't
+//! have a crate yet, that's created first and registered with the workspace and the `aoc` runner.
+
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+const DAY_MODULE_TEMPLATE: &str = r#"use advent_of_code_common::{Input, Solution, error::ParseError};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    type Input = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Input::new(input)
+            .non_empty_lines()
+            .map(|(_, line)| line.to_string())
+            .collect())
+    }
+
+    fn part1(_lines: &Self::Input) -> Self::Answer1 {
+        todo!("Day {day} part 1")
+    }
+
+    fn part2(_lines: &Self::Input) -> Self::Answer2 {
+        todo!("Day {day} part 2")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // TODO: Paste the example input from the puzzle description
+    const EXAMPLE: &str = "\
+";
+
+    #[test]
+    #[ignore = "example answer not filled in yet"]
+    fn part1_example() {
+        assert_eq!(Day{day}::solve_part_1(EXAMPLE).unwrap(), 0);
+    }
+
+    #[test]
+    #[ignore = "example answer not filled in yet"]
+    fn part2_example() {
+        assert_eq!(Day{day}::solve_part_2(EXAMPLE).unwrap(), 0);
+    }
+}
+"#;
+
+const DAY_BIN_TEMPLATE: &str = "\
+use advent_of_code_common::memory::CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn main() {
+    {lib}::runner::day_main({day});
+}
+";
+
+const YEAR_CARGO_TEMPLATE: &str = r#"[package]
+name = "advent-of-code-{year}"
+version = "0.1.0"
+edition = "2024"
+
+[lib]
+name = "advent_of_code_{year}"
+path = "src/lib.rs"
+
+[dependencies]
+advent-of-code-common = { path = "../common" }
+"#;
+
+const YEAR_LIB_TEMPLATE: &str = "\
+//! Solutions for Advent of Code {year}.
+
+pub mod runner;
+";
+
+// `DAYS` starts empty, but the first day is always added straight away
+const YEAR_RUNNER_TEMPLATE: &str = "\
+//! Registers this year's days with the shared runner.
+
+use advent_of_code_common::runner::{self, Day, Year, day};
+
+use crate::*;
+
+/// All days that have been solved so far, in order.
+pub const DAYS: &[Day] = &[
+];
+
+pub const YEAR: Year = Year {
+    number: {year},
+    days: DAYS,
+};
+
+/// Shared `main` for the individual `dayN` binaries.
+pub fn day_main(day_number: u8) {
+    runner::day_main(&YEAR, day_number);
+}
+";
+
+const YEAR_README_TEMPLATE: &str = "\
+# Advent of Code {year}
+
+This folder contains my personal solutions for Advent of Code {year}.
+
+## Solution Notes
+
+Key:
+
+- ⭐ - Solved without external assistance
+- ❗ - Used hints
+- 💼 - Used external libraries
+
+| Day | Part 1 | Part 2 |
+|---|---|---|
+";
+
+/// Finds the workspace root, i.e. the current directory or its parent.
+pub fn find_workspace_dir() -> io::Result<PathBuf> {
+    [Path::new("."), Path::new("..")]
+        .into_iter()
+        .find(|dir| {
+            fs::read_to_string(dir.join("Cargo.toml"))
+                .is_ok_and(|manifest| manifest.lines().any(|l| l.trim() == "[workspace]"))
+        })
+        .map(Path::to_path_buf)
+        .ok_or_else(|| io::Error::new(ErrorKind::NotFound, "Unable to find the workspace"))
+}
+
+/// Creates the crate for a new year in the workspace at `workspace_dir`, and registers it with
+/// the workspace and the `aoc` runner (as a dependency and in its `YEARS` list). Returns the
+/// files that were created or modified.
+///
+/// The crate has no days yet, so [`scaffold_day`] should be run on it straight after.
+pub fn scaffold_year(workspace_dir: &Path, year: u16) -> io::Result<Vec<PathBuf>> {
+    let crate_dir = workspace_dir.join(year.to_string());
+    if crate_dir.exists() {
+        return Err(io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!("'{}' already exists", crate_dir.display()),
+        ));
+    }
+
+    let edit = |relative_path: &str, insert: fn(&str, u16) -> Result<String, String>| {
+        let path = workspace_dir.join(relative_path);
+        let contents = insert(&fs::read_to_string(&path)?, year).map_err(|message| {
+            io::Error::new(
+                ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), message),
+            )
+        })?;
+
+        Ok::<_, io::Error>((path, contents))
+    };
+
+    let edits = [
+        edit("Cargo.toml", insert_workspace_member)?,
+        edit("aoc/Cargo.toml", insert_year_dependency)?,
+        edit("aoc/src/main.rs", insert_runner_year)?,
+    ];
+
+    let files = [
+        ("Cargo.toml", YEAR_CARGO_TEMPLATE),
+        ("src/lib.rs", YEAR_LIB_TEMPLATE),
+        ("src/runner.rs", YEAR_RUNNER_TEMPLATE),
+        ("README.md", YEAR_README_TEMPLATE),
+    ];
+
+    fs::create_dir_all(crate_dir.join("src/bin"))?;
+
+    let mut changed = Vec::new();
+    for (relative_path, template) in files {
+        let path = crate_dir.join(relative_path);
+        fs::write(&path, template.replace("{year}", &year.to_string()))?;
+        changed.push(path);
+    }
+
+    for (path, contents) in edits {
+        fs::write(&path, contents)?;
+        changed.push(path);
+    }
+
+    Ok(changed)
+}
+
+/// Finds the crate for a year, i.e. the current directory or a `<year>` folder in the workspace.
+pub fn find_year_dir(year: u16) -> io::Result<PathBuf> {
+    let package_line = format!("name = \"advent-of-code-{}\"", year);
+    let year_dir = year.to_string();
+
+    [
+        Path::new("."),
+        Path::new(&year_dir),
+        &Path::new("..").join(&year_dir),
+    ]
+    .into_iter()
+    .find(|dir| {
+        fs::read_to_string(dir.join("Cargo.toml"))
+            .is_ok_and(|manifest| manifest.lines().any(|l| l.trim() == package_line))
+    })
+    .map(Path::to_path_buf)
+    .ok_or_else(|| {
+        io::Error::new(
+            ErrorKind::NotFound,
+            format!("Unable to find the crate for {}", year),
+        )
+    })
+}
+
+/// Creates and registers a new day in the crate at `crate_dir`, returning the files that were
+/// created or modified.
+///
+/// Everything is checked before anything is written, so a failure leaves the crate untouched.
+pub fn scaffold_day(crate_dir: &Path, year: u16, day: u8) -> io::Result<Vec<PathBuf>> {
+    let lib = format!("advent_of_code_{}", year);
+
+    let module_path = crate_dir.join(format!("src/day{}.rs", day));
+    let bin_path = crate_dir.join(format!("src/bin/day{}.rs", day));
+    for path in [&module_path, &bin_path] {
+        if path.exists() {
+            return Err(io::Error::new(
+                ErrorKind::AlreadyExists,
+                format!("'{}' already exists", path.display()),
+            ));
+        }
+    }
+
+    let edit = |relative_path: &str, insert: fn(&str, u8) -> Result<String, String>| {
+        let path = crate_dir.join(relative_path);
+        let contents = insert(&fs::read_to_string(&path)?, day).map_err(|message| {
+            io::Error::new(
+                ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), message),
+            )
+        })?;
+
+        Ok::<_, io::Error>((path, contents))
+    };
+
+    let edits = [
+        edit("src/lib.rs", insert_module)?,
+        edit("src/runner.rs", insert_runner_day)?,
+        edit("Cargo.toml", insert_bin_target)?,
+        edit("README.md", insert_readme_row)?,
+    ];
+
+    fs::write(&module_path, render(DAY_MODULE_TEMPLATE, &lib, day))?;
+    fs::write(&bin_path, render(DAY_BIN_TEMPLATE, &lib, day))?;
+
+    let mut changed = vec![module_path, bin_path];
+    for (path, contents) in edits {
+        fs::write(&path, contents)?;
+        changed.push(path);
+    }
+
+    Ok(changed)
+}
+
+fn render(template: &str, lib: &str, day: u8) -> String {
+    template
+        .replace("{lib}", lib)
+        .replace("{day}", &day.to_string())
+}
+
+/// Adds `pub mod dayN;` to the (lexically sorted) module list in `lib.rs`.
+fn insert_module(lib_rs: &str, day: u8) -> Result<String, String> {
+    let new_line = format!("pub mod day{};", day);
+    if lib_rs.lines().any(|l| l == new_line) {
+        return Err(format!("Day {} is already declared", day));
+    }
+
+    // Compare module names rather than whole lines, to match rustfmt's ordering (day1 < day10)
+    let module_name = |line: &str| {
+        line.strip_prefix("pub mod ")
+            .and_then(|rest| rest.strip_suffix(';'))
+            .map(str::to_string)
+    };
+    let new_name = format!("day{}", day);
+
+    let mut lines: Vec<&str> = lib_rs.lines().collect();
+    let insert_idx = lines
+        .iter()
+        .position(|l| module_name(l).is_some_and(|name| name > new_name))
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|l| module_name(l).is_some())
+                .map(|idx| idx + 1)
+        })
+        .ok_or("No module declarations found")?;
+
+    lines.insert(insert_idx, &new_line);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the day to the end of `runner::DAYS`.
+fn insert_runner_day(runner_rs: &str, day: u8) -> Result<String, String> {
+    let mut lines: Vec<&str> = runner_rs.lines().collect();
+    let days_start = lines
+        .iter()
+        .position(|l| l.starts_with("pub const DAYS"))
+        .ok_or("No DAYS list found")?;
+
+    // rustfmt puts a short list (e.g. a new year's first day) on one line, so split it up first
+    let days_line = lines[days_start];
+    if let Some((head, entries)) = days_line
+        .split_once("= &[")
+        .filter(|_| days_line.ends_with("];"))
+    {
+        let entries = entries.trim_end_matches("];").trim_end_matches(',');
+        let expanded = match entries.trim() {
+            "" => format!("{}= &[\n];", head),
+            entries => format!("{}= &[\n    {},\n];", head, entries),
+        };
+
+        let expanded_rs = runner_rs.replacen(days_line, &expanded, 1);
+        return insert_runner_day(&expanded_rs, day);
+    }
+
+    let days_end = lines[days_start..]
+        .iter()
+        .position(|l| *l == "];")
+        .ok_or("DAYS list isn't terminated")?;
+
+    // Entries can span several lines (e.g. with `.with_generator(...)`), so look at the day
+    // numbers rather than whole lines
+    let days_list = lines[days_start..(days_start + days_end)].join("\n");
+    if registered_days(&days_list).any(|d| d == day) {
+        return Err(format!("Day {} is already registered", day));
+    }
+
+    let new_line = format!("    day::<day{0}::Day{0}>({0}),", day);
+    lines.insert(days_start + days_end, &new_line);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Day numbers of the `day::<...>(N)` entries in the `DAYS` list.
+fn registered_days(days_list: &str) -> impl Iterator<Item = u8> + '_ {
+    days_list.split("day::<").skip(1).filter_map(|entry| {
+        let (_, rest) = entry.split_once(">(")?;
+        let (number, _) = rest.split_once(')')?;
+        number.trim().parse().ok()
+    })
+}
+
+/// Adds a `[[bin]]` target after the last one in `Cargo.toml`.
+fn insert_bin_target(cargo_toml: &str, day: u8) -> Result<String, String> {
+    let name_line = format!("name = \"day{}\"", day);
+    if cargo_toml.lines().any(|l| l == name_line) {
+        return Err(format!("Binary for day {} already exists", day));
+    }
+
+    let path_line = format!("path = \"src/bin/day{}.rs\"", day);
+    let mut lines: Vec<&str> = cargo_toml.lines().collect();
+
+    // Binaries go after the last one, or before the dependencies for a new year's crate
+    let insert_idx = match lines.iter().rposition(|l| *l == "[[bin]]") {
+        Some(last_bin) => lines[last_bin..]
+            .iter()
+            .position(|l| l.trim().is_empty())
+            .map_or(lines.len(), |idx| last_bin + idx),
+        None => lines
+            .iter()
+            .position(|l| *l == "[dependencies]")
+            .map(|idx| idx - 1)
+            .ok_or("No [[bin]] targets or [dependencies] found")?,
+    };
+
+    lines.splice(
+        insert_idx..insert_idx,
+        ["", "[[bin]]", &name_line, &path_line],
+    );
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds an unsolved row to the end of the solution notes table in the README.
+fn insert_readme_row(readme: &str, day: u8) -> Result<String, String> {
+    let row_prefix = format!("| **{}** |", day);
+    if readme.lines().any(|l| l.starts_with(&row_prefix)) {
+        return Err(format!("Day {} already has a row", day));
+    }
+
+    let new_row = format!("{} | |", row_prefix);
+    let mut lines: Vec<&str> = readme.lines().collect();
+
+    // A new year's table has no rows yet, just the header and its separator
+    let last_row = lines
+        .iter()
+        .rposition(|l| l.starts_with("| **"))
+        .or_else(|| lines.iter().position(|l| l.starts_with("|---")))
+        .ok_or("No solution table found")?;
+
+    lines.insert(last_row + 1, &new_row);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the year's folder to the workspace `members`, which is kept on one line.
+fn insert_workspace_member(cargo_toml: &str, year: u16) -> Result<String, String> {
+    let member = format!("\"{}\"", year);
+    let mut lines: Vec<String> = cargo_toml.lines().map(str::to_string).collect();
+
+    let members_line = lines
+        .iter_mut()
+        .find(|l| l.starts_with("members = [") && l.ends_with(']'))
+        .ok_or("No members list found")?;
+
+    if members_line.contains(&member) {
+        return Err(format!("{} is already a workspace member", year));
+    }
+
+    members_line.insert_str(members_line.len() - 1, &format!(", {}", member));
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the year's crate to the `aoc` runner's dependencies, after the other years.
+fn insert_year_dependency(cargo_toml: &str, year: u16) -> Result<String, String> {
+    let prefix = format!("advent-of-code-{} ", year);
+    if cargo_toml.lines().any(|l| l.starts_with(&prefix)) {
+        return Err(format!("{} is already a dependency", year));
+    }
+
+    let new_line = format!("{}= {{ path = \"../{}\" }}", prefix, year);
+    let mut lines: Vec<&str> = cargo_toml.lines().collect();
+
+    // Year crates are named by number, unlike `advent-of-code-common`
+    let is_year = |l: &str| {
+        l.strip_prefix("advent-of-code-")
+            .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+    };
+    let insert_idx = lines
+        .iter()
+        .rposition(|l| is_year(l))
+        .map(|idx| idx + 1)
+        .ok_or("No year dependencies found")?;
+
+    lines.insert(insert_idx, &new_line);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the year to the end of the runner's `YEARS` list, formatted the way rustfmt would.
+fn insert_runner_year(main_rs: &str, year: u16) -> Result<String, String> {
+    let mut lines: Vec<&str> = main_rs.lines().collect();
+    let years_start = lines
+        .iter()
+        .position(|l| l.starts_with("const YEARS"))
+        .ok_or("No YEARS list found")?;
+    let years_len = lines[years_start..]
+        .iter()
+        .position(|l| l.ends_with("];"))
+        .ok_or("YEARS list isn't terminated")?
+        + 1;
+
+    let declaration = lines[years_start..(years_start + years_len)].join("\n");
+    let (head, list) = declaration
+        .split_once("= &[")
+        .ok_or("YEARS list isn't a slice")?;
+
+    let mut entries: Vec<String> = list
+        .trim_end_matches("];")
+        .split(',')
+        .map(|entry| entry.trim().to_string())
+        .filter(|entry| !entry.is_empty())
+        .collect();
+
+    let new_entry = format!("&advent_of_code_{}::runner::YEAR", year);
+    if entries.contains(&new_entry) {
+        return Err(format!("{} is already in the YEARS list", year));
+    }
+    entries.push(new_entry);
+
+    // rustfmt keeps the list on one line if it's short (`array_width`), otherwise it puts each
+    // entry on its own line
+    let one_line = format!("{}= &[{}];", head, entries.join(", "));
+    let new_declaration = if entries.join(", ").len() <= 60 && one_line.len() <= 100 {
+        one_line
+    } else {
+        let entry_lines: String = entries.iter().map(|e| format!("    {},\n", e)).collect();
+        format!("{}= &[\n{}];", head, entry_lines)
+    };
+
+    lines.splice(
+        years_start..(years_start + years_len),
+        new_declaration.lines(),
+    );
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_modules_and_days() {
+        let lib_rs =
+            "pub mod bench;\npub mod day1;\npub mod day12;\npub mod day2;\npub mod error;\n";
+        assert_eq!(
+            insert_module(lib_rs, 13).unwrap(),
+            "pub mod bench;\npub mod day1;\npub mod day12;\npub mod day13;\npub mod day2;\npub mod error;\n"
+        );
+        assert!(insert_module(lib_rs, 2).is_err());
+        assert_eq!(
+            insert_module("pub mod day1;\npub mod day2;\n", 10).unwrap(),
+            "pub mod day1;\npub mod day10;\npub mod day2;\n"
+        );
+
+        let runner_rs = "pub const DAYS: &[Day] = &[\n    day::<day1::Day1>(1),\n];\n";
+        assert_eq!(
+            insert_runner_day(runner_rs, 2).unwrap(),
+            "pub const DAYS: &[Day] = &[\n    day::<day1::Day1>(1),\n    day::<day2::Day2>(2),\n];\n"
+        );
+        assert!(insert_runner_day(runner_rs, 1).is_err());
+
+        let runner_rs =
+            "pub const DAYS: &[Day] = &[day::<day1::Day1>(1)];\n\npub const YEAR: u16 = 2026;\n";
+        assert_eq!(
+            insert_runner_day(runner_rs, 2).unwrap(),
+            "pub const DAYS: &[Day] = &[\n    day::<day1::Day1>(1),\n    day::<day2::Day2>(2),\n];\n\npub const YEAR: u16 = 2026;\n"
+        );
+        assert!(insert_runner_day(runner_rs, 1).is_err());
+    }
+
+    #[test]
+    fn finds_days_registered_with_generators() {
+        let runner_rs = "\
+pub const DAYS: &[Day] = &[
+    day::<day1::Day1>(1)
+        .with_generator(generator::day1)
+        .with_exporter(day1::export),
+    day::<day2::Day2>(2).with_generator(generator::day2),
+];
+";
+
+        for day in [1, 2] {
+            assert_eq!(
+                insert_runner_day(runner_rs, day),
+                Err(format!("Day {} is already registered", day))
+            );
+        }
+
+        assert!(
+            insert_runner_day(runner_rs, 12)
+                .unwrap()
+                .ends_with("    day::<day2::Day2>(2).with_generator(generator::day2),\n    day::<day12::Day12>(12),\n];\n")
+        );
+    }
+
+    #[test]
+    fn adds_bin_targets_and_readme_rows() {
+        let cargo_toml = "\
+[[bin]]
+name = \"day1\"
+path = \"src/bin/day1.rs\"
+
+[dependencies]
+";
+        assert_eq!(
+            insert_bin_target(cargo_toml, 2).unwrap(),
+            "\
+[[bin]]
+name = \"day1\"
+path = \"src/bin/day1.rs\"
+
+[[bin]]
+name = \"day2\"
+path = \"src/bin/day2.rs\"
+
+[dependencies]
+"
+        );
+        assert!(insert_bin_target(cargo_toml, 1).is_err());
+
+        let readme =
+            "| Day | Part 1 | Part 2 |\n|---|---|---|\n| **1** | :star: | :star: |\n\n### Day 1\n";
+        assert_eq!(
+            insert_readme_row(readme, 2).unwrap(),
+            "| Day | Part 1 | Part 2 |\n|---|---|---|\n| **1** | :star: | :star: |\n| **2** | | |\n\n### Day 1\n"
+        );
+    }
+
+    #[test]
+    fn registers_new_years() {
+        assert_eq!(
+            insert_workspace_member("[workspace]\nmembers = [\"aoc\", \"2025\"]\n", 2026).unwrap(),
+            "[workspace]\nmembers = [\"aoc\", \"2025\", \"2026\"]\n"
+        );
+        assert!(insert_workspace_member("members = [\"2025\"]\n", 2025).is_err());
+
+        let cargo_toml = "\
+[dependencies]
+advent-of-code-common = { path = \"../common\" }
+advent-of-code-2025 = { path = \"../2025\" }
+";
+        assert_eq!(
+            insert_year_dependency(cargo_toml, 2026).unwrap(),
+            cargo_toml.to_string() + "advent-of-code-2026 = { path = \"../2026\" }\n"
+        );
+        assert!(insert_year_dependency(cargo_toml, 2025).is_err());
+
+        let main_rs = "/// Years\nconst YEARS: &[&Year] = &[&advent_of_code_2025::runner::YEAR];\n\nfn main() {}\n";
+        let main_rs = insert_runner_year(main_rs, 2026).unwrap();
+        assert_eq!(
+            main_rs,
+            "\
+/// Years
+const YEARS: &[&Year] = &[
+    &advent_of_code_2025::runner::YEAR,
+    &advent_of_code_2026::runner::YEAR,
+];
+
+fn main() {}
+"
+        );
+        assert!(insert_runner_year(&main_rs, 2027).unwrap().contains(
+            "    &advent_of_code_2026::runner::YEAR,\n    &advent_of_code_2027::runner::YEAR,\n];\n"
+        ));
+        assert!(insert_runner_year(&main_rs, 2026).is_err());
+    }
+
+    #[test]
+    fn scaffolds_the_first_day_of_a_year() {
+        let cargo_toml = YEAR_CARGO_TEMPLATE.replace("{year}", "2026");
+        assert!(
+            insert_bin_target(&cargo_toml, 1)
+                .unwrap()
+                .contains("path = \"src/lib.rs\"\n\n[[bin]]\nname = \"day1\"\npath = \"src/bin/day1.rs\"\n\n[dependencies]\n")
+        );
+
+        assert_eq!(
+            insert_module(YEAR_LIB_TEMPLATE, 1).unwrap(),
+            "//! Solutions for Advent of Code {year}.\n\npub mod day1;\npub mod runner;\n"
+        );
+        assert!(
+            insert_runner_day(YEAR_RUNNER_TEMPLATE, 1)
+                .unwrap()
+                .contains("pub const DAYS: &[Day] = &[\n    day::<day1::Day1>(1),\n];\n")
+        );
+        assert!(
+            insert_readme_row(YEAR_README_TEMPLATE, 1)
+                .unwrap()
+                .ends_with("|---|---|---|\n| **1** | | |\n")
+        );
+    }
+
+    #[test]
+    fn renders_templates() {
+        let module = render(DAY_MODULE_TEMPLATE, "advent_of_code_2026", 3);
+        assert!(module.contains("pub struct Day3;"));
+        assert!(module.contains("Day3::solve_part_2(EXAMPLE)"));
+
+        let bin = render(DAY_BIN_TEMPLATE, "advent_of_code_2026", 3);
+        assert!(bin.contains("advent_of_code_2026::runner::day_main(3);"));
+    }
+}
@@ -0,0 +1,60 @@
+//! Extra subcommands that a day can register for exploring its puzzle beyond the two answers,
+//! e.g. `aoc lock` for 2025 day 1. The runner finds the tool by name, reads the input and prints
+//! whatever the tool returns, so a year-generic binary doesn't need to know about any of them.
+
+use std::fmt::{self, Display};
+
+use crate::error::ParseError;
+
+/// A named subcommand, run as `aoc <name> [--year YEAR] [options] <input>`.
+#[derive(Clone, Copy, Debug)]
+pub struct Tool {
+    pub name: &'static str,
+    /// Options the tool accepts, e.g. `[--target COMBINATION]`.
+    pub options: &'static str,
+    /// What the tool does, shown under its usage line.
+    pub description: &'static str,
+    pub run: ToolFn,
+}
+
+/// Runs a tool on the input text with the options given before the input path, returning the
+/// lines to print.
+pub type ToolFn = fn(&str, &[String]) -> Result<Vec<String>, ToolError>;
+
+/// Reasons a tool can fail.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ToolError {
+    /// The options were invalid, so the usage should be shown.
+    Usage(String),
+    /// The input couldn't be parsed.
+    Input(ParseError),
+    /// The input was valid, but the tool couldn't run on it.
+    Failed(String),
+}
+
+impl From<ParseError> for ToolError {
+    fn from(error: ParseError) -> Self {
+        ToolError::Input(error)
+    }
+}
+
+impl Display for ToolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ToolError::Usage(message) | ToolError::Failed(message) => write!(f, "{}", message),
+            ToolError::Input(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Tool {
+    /// Usage line and description, in the same layout as the rest of `aoc`'s usage.
+    pub fn usage(&self) -> String {
+        format!(
+            "  aoc {} [--year YEAR] {} <input>\n      {}",
+            self.name,
+            self.options,
+            self.description.replace('\n', "\n      ")
+        )
+    }
+}
@@ -0,0 +1,86 @@
+//! Leveled tracing for diagnosing wrong answers, enabled with `-v` (debug) or `-vv` (trace).
+//!
+//! Solvers log step-by-step events with [`debug!`](crate::debug) and [`trace!`](crate::trace),
+//! which print to stderr so they never mix with the answers. When tracing is disabled, the only
+//! cost is a relaxed atomic load: the message isn't formatted and its arguments aren't evaluated.
+//!
+//! ```
+//! use advent_of_code_common::{debug, trace};
+//!
+//! let (position, zeroes) = (52, 3);
+//! debug!("Zeroes so far: {}", zeroes);
+//! trace!("Position: {:>2}, Zeroes: {}", position, zeroes);
+//! ```
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// How much detail to log, from least to most.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[repr(u8)]
+pub enum Level {
+    /// Summaries of intermediate results, enabled by `-v`.
+    Debug = 1,
+    /// Every step of a solver, enabled by `-vv`.
+    Trace = 2,
+}
+
+/// Sets the most detailed level that gets logged, or disables tracing with `None`.
+pub fn set_level(level: Option<Level>) {
+    LEVEL.store(level.map_or(0, |l| l as u8), Ordering::Relaxed);
+}
+
+/// Whether events at the given level are logged.
+#[inline]
+pub fn enabled(level: Level) -> bool {
+    LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Logs a summary event to stderr if `-v` or `-vv` is given.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        if $crate::trace::enabled($crate::trace::Level::Debug) {
+            eprintln!("[debug] {}", format_args!($($arg)+));
+        }
+    };
+}
+
+/// Logs a step-by-step event to stderr if `-vv` is given.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        if $crate::trace::enabled($crate::trace::Level::Trace) {
+            eprintln!("[trace] {}", format_args!($($arg)+));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn only_evaluates_enabled_levels() {
+        let evaluations = Cell::new(0);
+        let expensive = || {
+            evaluations.set(evaluations.get() + 1);
+            "value"
+        };
+
+        set_level(None);
+        crate::debug!("{}", expensive());
+        assert_eq!(evaluations.get(), 0);
+
+        set_level(Some(Level::Debug));
+        crate::debug!("{}", expensive());
+        crate::trace!("{}", expensive());
+        assert_eq!(evaluations.get(), 1);
+        assert!(!enabled(Level::Trace));
+
+        set_level(None);
+    }
+}